@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use advent_of_code::hash::{FastCollection, FastMap};
+use advent_of_code::parse::{lines_with, ParseError};
 
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut first_vec, mut second_vec) = parse_input(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (mut first_vec, mut second_vec) = parse_input(input)?;
 
     first_vec.sort();
     second_vec.sort();
@@ -14,13 +15,13 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|x| x.0.abs_diff(*x.1))
         .sum();
 
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (first_vec, second_vec) = parse_input(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let (first_vec, second_vec) = parse_input(input)?;
 
-    let mut hash_map = HashMap::new();
+    let mut hash_map = FastMap::new();
 
     for el in second_vec {
         *hash_map.entry(el).or_insert(0) += 1;
@@ -31,18 +32,20 @@ pub fn part_two(input: &str) -> Option<u32> {
         .map(|x| x * hash_map.get(x).unwrap_or(&0))
         .sum();
 
-    Some(similarity)
+    Ok(similarity)
 }
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input
-        .lines()
-        .map(|x| {
-            let mut number_it = x.split_whitespace().map(|x| x.parse::<u32>().unwrap());
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
+    let pairs: Vec<(u32, u32)> = lines_with(input, |line| {
+        let mut number_it = line.split_whitespace().map(str::parse::<u32>);
 
-            (number_it.next().unwrap(), number_it.next().unwrap())
-        })
-        .unzip()
+        match (number_it.next(), number_it.next(), number_it.next()) {
+            (Some(Ok(first)), Some(Ok(second)), None) => Ok((first, second)),
+            _ => Err("expected two numbers"),
+        }
+    })?;
+
+    Ok(pairs.into_iter().unzip())
 }
 
 #[cfg(test)]
@@ -52,12 +55,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result, Ok(11));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        assert_eq!(result, Ok(31));
     }
 }
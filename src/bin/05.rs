@@ -1,4 +1,6 @@
-use std::{cmp::Ordering, collections::HashSet};
+use std::cmp::Ordering;
+
+use advent_of_code::hash::{FastCollection, FastSet};
 
 advent_of_code::solution!(5);
 
@@ -42,11 +44,11 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn calculate_result<F>(input: &str, mut calculation: F) -> ()
 where
-    F: FnMut(&mut [u32], &HashSet<&str>),
+    F: FnMut(&mut [u32], &FastSet<&str>),
 {
     let (ordering_rules_str, updates_str) = input.split_once("\n\n").unwrap();
 
-    let mut less_than_set = HashSet::new();
+    let mut less_than_set = FastSet::new();
 
     for line in ordering_rules_str.lines() {
         less_than_set.insert(line);
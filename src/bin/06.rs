@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::hash::{FastCollection, FastMap, FastSet};
+use advent_of_code::visualize::Visualizer;
 
 advent_of_code::solution!(6);
 
@@ -33,7 +34,7 @@ impl Direction {
 #[derive(Clone)]
 struct Grid {
     data: Vec<char>,
-    direction_data: HashMap<usize, HashSet<Direction>>,
+    direction_data: FastMap<usize, FastSet<Direction>>,
     width: usize,
     height: usize,
 }
@@ -54,6 +55,16 @@ impl Grid {
         (idx % self.width, idx / self.width)
     }
 
+    /// Renders the grid with the guard at `guard`.
+    fn to_str(&self, guard: &(usize, usize)) -> String {
+        let mut data = self.data.clone();
+        data[self.width * guard.1 + guard.0] = '^';
+        data.chunks(self.width)
+            .map(|chunk| chunk.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn out_of_bounds(&self, pos: &(i32, i32)) -> bool {
         pos.0 < 0 || pos.0 >= self.width as i32 || pos.1 < 0 || pos.1 >= self.height as i32
     }
@@ -62,7 +73,7 @@ impl Grid {
         let idx = self.width * pos.1 + pos.0;
         self.direction_data
             .entry(idx)
-            .or_insert_with(HashSet::new) // Insert an empty HashSet if not present
+            .or_insert_with(FastSet::new) // Insert an empty FastSet if not present
             .insert(dir.clone());
     }
 
@@ -95,7 +106,7 @@ impl Guard {
 pub fn part_one(input: &str) -> Option<u32> {
     let mut grid = Grid {
         data: input.lines().flat_map(|s| s.chars()).collect(),
-        direction_data: HashMap::new(),
+        direction_data: FastMap::new(),
         width: input.lines().next().unwrap().len(),
         height: input.lines().count(),
     };
@@ -107,11 +118,14 @@ pub fn part_one(input: &str) -> Option<u32> {
     };
 
     let mut unique_positions = 0;
+    let mut vis = Visualizer::new();
 
     grid.replace_at(&guard.pos, 'X');
     unique_positions += 1;
 
     while guard.in_grid {
+        vis.frame(|| grid.to_str(&guard.pos));
+
         let next_pos = guard.next_pos();
 
         if grid.out_of_bounds(&next_pos) {
@@ -143,7 +157,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let mut grid = Grid {
         data: input.lines().flat_map(|s| s.chars()).collect(),
-        direction_data: HashMap::new(),
+        direction_data: FastMap::new(),
         width: input.lines().next().unwrap().len(),
         height: input.lines().count(),
     };
@@ -1,10 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::grid::{Point, PointSet};
+use advent_of_code::hash::{FastCollection, FastMap};
+use advent_of_code::math::gcd;
 
 advent_of_code::solution!(8);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut antenna_map = HashMap::new();
-    let mut antinode_set = HashSet::new();
+    let mut antenna_map = FastMap::new();
+    let mut antinode_set = PointSet::new();
 
     let _ = input.lines().enumerate().for_each(|(i, line)| {
         line.chars()
@@ -29,11 +31,11 @@ pub fn part_one(input: &str) -> Option<u32> {
                 let a_2 = (i_1 as isize + i_diff, j_1 as isize + j_diff);
 
                 if is_in_bounds(grid_dims, a_1) {
-                    antinode_set.insert(format!("{},{}", a_1.0, a_1.1));
+                    antinode_set.insert(Point::new(a_1.1, a_1.0));
                 }
 
                 if is_in_bounds(grid_dims, a_2) {
-                    antinode_set.insert(format!("{},{}", a_2.0, a_2.1));
+                    antinode_set.insert(Point::new(a_2.1, a_2.0));
                 }
             }
         }
@@ -43,8 +45,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut antenna_map = HashMap::new();
-    let mut antinode_set = HashSet::new();
+    let mut antenna_map = FastMap::new();
+    let mut antinode_set = PointSet::new();
 
     let _ = input.lines().enumerate().for_each(|(i, line)| {
         line.chars()
@@ -71,7 +73,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
                 let mut a = (i_2 as isize, j_2 as isize);
                 loop {
-                    antinode_set.insert(format!("{},{}", a.0, a.1));
+                    antinode_set.insert(Point::new(a.1, a.0));
                     a = (a.0 - i_diff, a.1 - j_diff);
 
                     if !is_in_bounds(grid_dims, a) {
@@ -86,7 +88,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     if !is_in_bounds(grid_dims, a) {
                         break;
                     } else {
-                        antinode_set.insert(format!("{},{}", a.0, a.1));
+                        antinode_set.insert(Point::new(a.1, a.0));
                     }
                 }
             }
@@ -100,14 +102,6 @@ fn is_in_bounds(dims: (usize, usize), point: (isize, isize)) -> bool {
     point.0 >= 0 && point.0 < dims.0 as isize && point.1 >= 0 && point.1 < dims.1 as isize
 }
 
-fn gcd(a: isize, b: isize) -> isize {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use advent_of_code::math::arithmetic_sum;
+
 advent_of_code::solution!(9);
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -19,7 +21,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     while start_idx <= end_idx {
         if !filling_empty {
-            checksum += start_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[start_idx]);
+            checksum += start_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[start_idx]);
             curr_pos += vec[start_idx];
             start_idx += 2;
 
@@ -28,18 +30,18 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
 
         if vec[empty_idx] < vec[end_idx] {
-            checksum += end_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[empty_idx]);
+            checksum += end_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[empty_idx]);
             curr_pos += vec[empty_idx];
             vec[end_idx] -= vec[empty_idx];
             empty_idx += 2;
             filling_empty = false;
         } else if vec[empty_idx] > vec[end_idx] {
-            checksum += end_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[end_idx]);
+            checksum += end_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[end_idx]);
             curr_pos += vec[end_idx];
             vec[empty_idx] -= vec[end_idx];
             end_idx -= 2;
         } else {
-            checksum += end_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[empty_idx]);
+            checksum += end_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[empty_idx]);
             curr_pos += vec[empty_idx];
             end_idx -= 2;
             empty_idx += 2;
@@ -76,7 +78,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         while empty_idx < end_idx {
             if vec[end_idx] <= space_vec[empty_idx / 2] {
                 curr_pos += vec[empty_idx] - space_vec[empty_idx / 2];
-                checksum += end_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[end_idx]);
+                checksum += end_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[end_idx]);
                 space_vec[empty_idx / 2] -= vec[end_idx];
                 moved_vec[end_idx / 2] = true;
 
@@ -93,7 +95,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     loop {
         if moved_vec[start_idx / 2] != true {
-            checksum += start_idx as u64 / 2 * sum(curr_pos, curr_pos + vec[start_idx]);
+            checksum += start_idx as u64 / 2 * arithmetic_sum(curr_pos, curr_pos + vec[start_idx]);
         }
 
         start_idx += 2;
@@ -107,10 +109,6 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(checksum)
 }
 
-fn sum(from: u64, to: u64) -> u64 {
-    (from..to).sum()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
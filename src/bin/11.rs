@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use advent_of_code::memo::FxMemo;
 
 advent_of_code::solution!(11);
 
@@ -16,14 +16,14 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut memoization = HashMap::new();
+    let mut memo = FxMemo::new();
     let sum = input
         .lines()
         .next()
         .unwrap()
         .split_ascii_whitespace()
         .map(|x| x.parse().unwrap())
-        .map(|num| transform_stone_memoization(num, 75, &mut memoization))
+        .map(|num| transform_stone_memoization(num, 75, &mut memo))
         .sum();
 
     Some(sum)
@@ -47,53 +47,22 @@ fn transform_stone(num: u64, blinks: u8) -> u64 {
     }
 }
 
-fn transform_stone_memoization(
-    num: u64,
-    blinks: u8,
-    memoization: &mut HashMap<String, u64>,
-) -> u64 {
+fn transform_stone_memoization(num: u64, blinks: u8, memo: &mut FxMemo<(u64, u8), u64>) -> u64 {
     if blinks == 0 {
-        1
-    } else {
-        if let Some(res) = memoization.get(&format!("{},{}", num, blinks)) {
-            return *res;
-        }
-
-        match num {
-            0 => {
-                let res = transform_stone_memoization(1, blinks - 1, memoization);
-                memoization
-                    .entry(format!("{},{}", 1, blinks - 1))
-                    .or_insert(res);
-
-                res
-            }
-            _ if (num as f32).log10().floor() as u32 % 2 == 1 => {
-                let digits = (num as f32).log10().floor() as u32 + 1;
-                let divisor = 10u64.pow(digits / 2);
-
-                let res_1 = transform_stone_memoization(num / divisor, blinks - 1, memoization);
-                memoization
-                    .entry(format!("{},{}", num / divisor, blinks - 1))
-                    .or_insert(res_1);
-
-                let res_2 = transform_stone_memoization(num % divisor, blinks - 1, memoization);
-                memoization
-                    .entry(format!("{},{}", num % divisor, blinks - 1))
-                    .or_insert(res_2);
+        return 1;
+    }
 
-                res_1 + res_2
-            }
-            _ => {
-                let res = transform_stone_memoization(num * 2024, blinks - 1, memoization);
-                memoization
-                    .entry(format!("{},{}", num * 2024, blinks - 1))
-                    .or_insert(res);
+    memo.get_or_compute((num, blinks), |memo| match num {
+        0 => transform_stone_memoization(1, blinks - 1, memo),
+        _ if (num as f32).log10().floor() as u32 % 2 == 1 => {
+            let digits = (num as f32).log10().floor() as u32 + 1;
+            let divisor = 10u64.pow(digits / 2);
 
-                res
-            }
+            transform_stone_memoization(num / divisor, blinks - 1, memo)
+                + transform_stone_memoization(num % divisor, blinks - 1, memo)
         }
-    }
+        _ => transform_stone_memoization(num * 2024, blinks - 1, memo),
+    })
 }
 
 #[cfg(test)]
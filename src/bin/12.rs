@@ -1,4 +1,6 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
+
+use advent_of_code::hash::{FastCollection, FastMap};
 
 advent_of_code::solution!(12);
 
@@ -261,7 +263,7 @@ fn find_region_price_by_sides(
         region_size += 1;
         *visit_grid.at_mut(&at) = VisitState::Visited;
 
-        let mut edge_map = HashMap::new();
+        let mut edge_map = FastMap::new();
 
         edge_map.insert(Direction::Up, false);
         edge_map.insert(Direction::Right, false);
@@ -305,7 +307,7 @@ fn find_region_price_by_sides(
 
 fn is_corner(
     grid: &Grid<char>,
-    edge_map: &HashMap<Direction, bool>,
+    edge_map: &FastMap<Direction, bool>,
     at: &(usize, usize),
     val: char,
     dir_1: Direction,
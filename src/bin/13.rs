@@ -1,10 +1,12 @@
+use advent_of_code::math::solve_2x2;
+use advent_of_code::parse::{uints, ParseError};
+
 advent_of_code::solution!(13);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let sum = input
-        .split("\n\n")
-        .map(|str| {
-            let nums = parse_numbers(str);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let sum = parse_machines(input)?
+        .iter()
+        .map(|nums| {
             if let Some((a, b)) =
                 solve_linear_system(nums[0], nums[2], nums[4], nums[1], nums[3], nums[5])
             {
@@ -15,14 +17,13 @@ pub fn part_one(input: &str) -> Option<u64> {
         })
         .sum();
 
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let sum = input
-        .split("\n\n")
-        .map(|str| {
-            let nums = parse_numbers(str);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let sum = parse_machines(input)?
+        .iter()
+        .map(|nums| {
             if let Some((a, b)) = solve_linear_system(
                 nums[0],
                 nums[2],
@@ -38,59 +39,37 @@ pub fn part_two(input: &str) -> Option<u64> {
         })
         .sum();
 
-    Some(sum)
+    Ok(sum)
 }
 
-fn parse_numbers(input: &str) -> Vec<u64> {
-    let mut numbers = Vec::new();
-    let mut curr_number = String::new();
-
-    for ch in input.chars() {
-        if ch.is_digit(10) {
-            curr_number.push(ch);
-        } else if !curr_number.is_empty() {
-            if let Ok(num) = curr_number.parse::<u64>() {
-                numbers.push(num);
-            }
-            curr_number.clear();
-        }
+/// Every machine is described by six numbers: the offsets of both buttons and the prize location.
+fn parse_machines(input: &str) -> Result<Vec<[u64; 6]>, ParseError> {
+    let nums = uints(input)?;
+
+    if nums.len() % 6 != 0 {
+        let end = &input[input.trim_end().len()..];
+        return Err(ParseError::at(
+            input,
+            end,
+            "expected six numbers per machine",
+        ));
     }
 
-    if !curr_number.is_empty() {
-        if let Ok(num) = curr_number.parse::<u64>() {
-            numbers.push(num);
-        }
-    }
-
-    numbers
+    Ok(nums
+        .chunks_exact(6)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect())
 }
 
 fn solve_linear_system(a1: u64, b1: u64, c1: u64, a2: u64, b2: u64, c2: u64) -> Option<(u64, u64)> {
-    let a1 = a1 as i64;
-    let b1 = b1 as i64;
-    let c1 = c1 as i64;
-    let a2 = a2 as i64;
-    let b2 = b2 as i64;
-    let c2 = c2 as i64;
-
-    let det = a1 * b2 - a2 * b1;
-
-    if det == 0 {
-        return None;
-    }
-
-    let x_num = c1 * b2 - c2 * b1;
-    let y_num = a1 * c2 - a2 * c1;
-
-    if x_num % det != 0 || y_num % det != 0 {
-        return None;
-    }
-
-    let x = x_num / det;
-    let y = y_num / det;
+    let [x, y] = solve_2x2(
+        [[a1 as i64, b1 as i64], [a2 as i64, b2 as i64]],
+        [c1 as i64, c2 as i64],
+    )?;
 
     Some((x as u64, y as u64))
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,12 +77,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
+        assert_eq!(result, Ok(480));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908));
+        assert_eq!(result, Ok(875318608908));
     }
 }
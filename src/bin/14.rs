@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::{ParseError, Pattern};
+use advent_of_code::template::Context;
+use advent_of_code::visualize::Visualizer;
+
 advent_of_code::solution!(14);
 
 struct Robot {
@@ -47,21 +51,13 @@ impl Grid {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let dims = (101, 103);
+pub fn part_one(input: &str, ctx: &Context) -> Result<u32, ParseError> {
+    let dims = dims(ctx);
 
     let mut quadrant_count_map: HashMap<u8, u32> = HashMap::new();
 
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split(|c: char| !c.is_digit(10) && c != '-')
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        let pos = (nums[0], nums[1]);
-        let vel = (nums[2], nums[3]);
-
-        let final_pos = calculate_board_position(dims, pos, vel, 100);
+    for robot in parse_robots(input)? {
+        let final_pos = calculate_board_position(dims, robot.pos, robot.vel, 100);
         if let Some(quadrant) = calculate_quandrant(dims, final_pos) {
             *quadrant_count_map.entry(quadrant).or_insert(0) += 1;
         }
@@ -69,55 +65,59 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     let prod = quadrant_count_map.values().fold(1, |acc, x| acc * x);
 
-    Some(prod)
+    Ok(prod)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let dims = (101, 103);
-
-    let mut robots = Vec::new();
+pub fn part_two(input: &str, ctx: &Context) -> Result<u32, String> {
+    let dims = dims(ctx);
+    let max_seconds: u32 = ctx.param_or("max_seconds", 1_000_000);
 
-    for line in input.lines() {
-        let nums: Vec<i32> = line
-            .split(|c: char| !c.is_digit(10) && c != '-')
-            .filter_map(|s| s.parse().ok())
-            .collect();
+    let mut robots = parse_robots(input).map_err(|e| e.to_string())?;
 
-        let pos = (nums[0], nums[1]);
-        let vel = (nums[2], nums[3]);
+    let mut vis = Visualizer::new();
 
-        robots.push(Robot { pos, vel });
-    }
-
-    for second in 1..1000001 {
+    for second in 1..=max_seconds {
         for i in 0..robots.len() {
             robots[i].pos = calculate_board_position(dims, robots[i].pos, robots[i].vel, 1);
         }
 
-        if print_grid_with_robots(dims, &robots, second) {
-            break;
-        };
+        let grid = grid_with_robots(dims, &robots);
+        vis.frame(|| format!("After {} seconds:\n{}", second, grid.to_str()));
+
+        // the tree is detected heuristically, run with `--visualize` to confirm it.
+        if grid.has_horizontal_line() {
+            return Ok(second);
+        }
     }
 
-    None
+    Err(format!("no tree found within {max_seconds} seconds"))
 }
 
-fn print_grid_with_robots(dims: (i32, i32), robots: &[Robot], second: u32) -> bool {
+fn parse_robots(input: &str) -> Result<Vec<Robot>, ParseError> {
+    let robots = Pattern::new("p={},{} v={},{}").parse_lines(input)?;
+
+    Ok(robots
+        .into_iter()
+        .map(|(px, py, vx, vy)| Robot {
+            pos: (px, py),
+            vel: (vx, vy),
+        })
+        .collect())
+}
+
+/// The examples use a smaller room than the real input.
+fn dims(ctx: &Context) -> (i32, i32) {
+    (ctx.param_or("width", 101), ctx.param_or("height", 103))
+}
+
+fn grid_with_robots(dims: (i32, i32), robots: &[Robot]) -> Grid {
     let mut grid = Grid::new((dims.0 as usize, dims.1 as usize));
 
     for robot in robots {
         *grid.at_mut(&(robot.pos.0 as usize, robot.pos.1 as usize)) = '*';
     }
 
-    if grid.has_horizontal_line() {
-        println!("After {} seconds:", second);
-        println!("{}", grid.to_str());
-        println!("");
-
-        return true;
-    }
-
-    false
+    grid
 }
 
 fn calculate_board_position(
@@ -152,13 +152,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_one(&input, &ctx);
+        assert_eq!(result, Ok(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_two(&input, &ctx);
+        assert!(result.is_err());
     }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::visualize::Visualizer;
+
 advent_of_code::solution!(15);
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
@@ -45,6 +47,20 @@ impl Grid<char> {
             height: str.lines().count(),
         }
     }
+
+    fn to_str(&self, robot_pos: (usize, usize)) -> String {
+        self.data
+            .chunks(self.width)
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, ch)| if (x, y) == robot_pos { '@' } else { *ch })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 impl<T> Grid<T> {
@@ -91,6 +107,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     let mut robot_pos = grid.find_first('@').unwrap();
     *grid.at_mut(robot_pos) = '.';
 
+    let mut vis = Visualizer::new();
+    vis.frame(|| grid.to_str(robot_pos));
+
     for dir in dirs
         .chars()
         .filter(|x| *x != '\n')
@@ -108,6 +127,8 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
             _ => panic!(),
         }
+
+        vis.frame(|| grid.to_str(robot_pos));
     }
 
     let res = grid
@@ -140,6 +161,9 @@ pub fn part_two(input: &str) -> Option<u32> {
     let mut robot_pos = grid.find_first('@').unwrap();
     *grid.at_mut(robot_pos) = '.';
 
+    let mut vis = Visualizer::new();
+    vis.frame(|| grid.to_str(robot_pos));
+
     for dir in dirs
         .chars()
         .filter(|x| *x != '\n')
@@ -157,6 +181,8 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
             _ => panic!(),
         }
+
+        vis.frame(|| grid.to_str(robot_pos));
     }
 
     let res = grid
@@ -1,7 +1,6 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use advent_of_code::hash::{FastCollection, FastMap, FastSet};
 
 advent_of_code::solution!(16);
 
@@ -149,7 +148,7 @@ impl PartialOrd for State {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let grid = Grid::from_str(input);
-    let mut visit_grid = HashMap::new();
+    let mut visit_grid = FastMap::new();
     for dir in Grid::<()>::ALL_DIRECTIONS {
         visit_grid.insert(dir, Grid::from_grid(&grid, |_| false));
     }
@@ -165,7 +164,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let grid = Grid::from_str(input);
-    let mut record_grid = HashMap::new();
+    let mut record_grid = FastMap::new();
     for dir in Grid::<()>::ALL_DIRECTIONS {
         record_grid.insert(dir, Grid::from_grid(&grid, |_| None));
     }
@@ -183,7 +182,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn find_best_path(
     grid: &Grid<char>,
-    visit_grid: &mut HashMap<Direction, Grid<bool>>,
+    visit_grid: &mut FastMap<Direction, Grid<bool>>,
     pos: GridPosition,
     end_pos: (usize, usize),
 ) -> Option<u32> {
@@ -233,16 +232,16 @@ fn find_best_path(
 
 fn find_best_tiles(
     grid: &Grid<char>,
-    visit_grid: &mut HashMap<Direction, Grid<Option<u32>>>,
+    visit_grid: &mut FastMap<Direction, Grid<Option<u32>>>,
     pos: GridPosition,
     end_pos: (usize, usize),
-) -> HashSet<(usize, usize)> {
+) -> FastSet<(usize, usize)> {
     let mut min_queue = BinaryHeap::new();
 
     min_queue.push(State { pos, cost: 0 });
 
     let mut best_cost = None;
-    let mut best_tiles = HashSet::new();
+    let mut best_tiles = FastSet::new();
 
     while let Some(state) = min_queue.pop() {
         if state.pos.pos == end_pos {
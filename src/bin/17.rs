@@ -1,6 +1,11 @@
+use std::fmt::{self, Display};
+
+use advent_of_code::parse::{blocks, ints, ParseError, Pattern};
+use advent_of_code::vm::{search_digits, Effect, InstructionSet, Machine, Status};
+
 advent_of_code::solution!(17);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum InstructionType {
     Adv,
     Bxl,
@@ -13,216 +18,150 @@ enum InstructionType {
 }
 
 impl InstructionType {
-    fn from(opcode: u8) -> Self {
+    fn from(opcode: i64) -> Option<Self> {
         match opcode {
-            0 => InstructionType::Adv,
-            1 => InstructionType::Bxl,
-            2 => InstructionType::Bst,
-            3 => InstructionType::Jnz,
-            4 => InstructionType::Bxc,
-            5 => InstructionType::Out,
-            6 => InstructionType::Bdv,
-            7 => InstructionType::Cdv,
-            _ => panic!(),
+            0 => Some(InstructionType::Adv),
+            1 => Some(InstructionType::Bxl),
+            2 => Some(InstructionType::Bst),
+            3 => Some(InstructionType::Jnz),
+            4 => Some(InstructionType::Bxc),
+            5 => Some(InstructionType::Out),
+            6 => Some(InstructionType::Bdv),
+            7 => Some(InstructionType::Cdv),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Instruction {
     typ: InstructionType,
-    operand: u8,
+    operand: i64,
 }
 
 impl Instruction {
-    fn from(opcode: u8, operand: u8) -> Self {
-        Instruction {
-            typ: InstructionType::from(opcode),
-            operand,
+    fn get_combo_operand(&self, registers: &[i64]) -> Result<i64, String> {
+        match self.operand {
+            0..4 => Ok(self.operand),
+            4..7 => Ok(registers[self.operand as usize - 4]),
+            _ => Err(format!("invalid combo operand {}", self.operand)),
         }
     }
 }
 
-#[derive(Debug, Clone)]
-struct DeviceState {
-    reg_a: u64,
-    reg_b: u64,
-    reg_c: u64,
-    instr_p: usize,
-    program: Vec<u8>,
-}
-
-impl DeviceState {
-    fn from_str(str: &str) -> Self {
-        let (reg_str, program_str) = str.split_once("\n\n").unwrap();
+impl InstructionSet for Instruction {
+    const REGISTERS: &'static [&'static str] = &["a", "b", "c"];
+    const WIDTH: usize = 2;
 
-        let reg_vals: Vec<_> = reg_str
-            .lines()
-            .map(|x| x.split_once(": ").unwrap().1)
-            .map(|x| x.parse().unwrap())
-            .collect();
-
-        let program = program_str
-            .split_once(": ")
-            .unwrap()
-            .1
-            .trim()
-            .split(',')
-            .filter_map(|x| x.parse().ok())
-            .collect();
+    fn decode(words: &[i64]) -> Result<Self, String> {
+        let typ = InstructionType::from(words[0]).ok_or(format!("invalid opcode {}", words[0]))?;
 
-        DeviceState {
-            reg_a: reg_vals[0],
-            reg_b: reg_vals[1],
-            reg_c: reg_vals[2],
-            instr_p: 0,
-            program,
-        }
+        Ok(Instruction {
+            typ,
+            operand: words[1],
+        })
     }
 
-    fn execute(&mut self) -> String {
-        let mut program_output = String::new();
-
-        while let Some(instr) = self.get_instruction() {
-            if let Some(output) = self.execute_instruction(instr) {
-                if program_output.is_empty() {
-                    program_output = output;
-                } else {
-                    program_output += format!(",{}", output).as_str();
-                }
-            }
-        }
-
-        program_output
-    }
-
-    fn get_instruction(&mut self) -> Option<Instruction> {
-        if self.instr_p < self.program.len() {
-            let opcode = self.program[self.instr_p];
-            self.instr_p += 1;
-            let operand = self.program[self.instr_p];
-            self.instr_p += 1;
-
-            Some(Instruction::from(opcode, operand))
-        } else {
-            None
-        }
-    }
-
-    fn get_combo_operand(&self, operand: u8) -> u64 {
-        match operand {
-            0..4 => operand as u64,
-            4 => self.reg_a,
-            5 => self.reg_b,
-            6 => self.reg_c,
-            _ => panic!(),
-        }
-    }
-
-    fn execute_instruction(&mut self, instr: Instruction) -> Option<String> {
-        match instr.typ {
+    fn execute(&self, registers: &mut [i64]) -> Result<Effect, String> {
+        match self.typ {
             InstructionType::Adv => {
-                self.reg_a = self.reg_a >> self.get_combo_operand(instr.operand);
-
-                None
+                registers[0] >>= self.get_combo_operand(registers)?;
             }
             InstructionType::Bxl => {
-                self.reg_b = self.reg_b ^ instr.operand as u64;
-
-                None
+                registers[1] ^= self.operand;
             }
             InstructionType::Bst => {
-                self.reg_b = self.get_combo_operand(instr.operand) % 8;
-
-                None
+                registers[1] = self.get_combo_operand(registers)? % 8;
             }
             InstructionType::Jnz => {
-                //if self.reg_a != 0 {
-                //    self.instr_p = instr.operand as usize;
-                //}
-
-                None
+                if registers[0] != 0 {
+                    // jumps address words, the machine addresses instructions of two words.
+                    return Ok(Effect::Jump(self.operand as usize / Self::WIDTH));
+                }
             }
             InstructionType::Bxc => {
-                self.reg_b = self.reg_b ^ self.reg_c;
-
-                None
+                registers[1] ^= registers[2];
             }
             InstructionType::Out => {
-                let output = (self.get_combo_operand(instr.operand) % 8).to_string();
-
-                Some(output)
+                return Ok(Effect::Output(self.get_combo_operand(registers)? % 8));
             }
             InstructionType::Bdv => {
-                self.reg_b = self.reg_a >> self.get_combo_operand(instr.operand);
-
-                None
+                registers[1] = registers[0] >> self.get_combo_operand(registers)?;
             }
             InstructionType::Cdv => {
-                self.reg_c = self.reg_a >> self.get_combo_operand(instr.operand);
-
-                None
+                registers[2] = registers[0] >> self.get_combo_operand(registers)?;
             }
         }
+
+        Ok(Effect::Next)
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let mut device = DeviceState::from_str(input);
-
-    let output = device.execute();
-
-    Some(output)
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = format!("{:?}", self.typ).to_lowercase();
+        write!(f, "{} {}", mnemonic, self.operand)
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let device = DeviceState::from_str(input);
-
-    let mut desired_output = device.program.clone();
-    let mut possible_a_vals = vec![];
-    let mut next_possible_a_vals = vec![];
-
-    let mut iter_count = 0;
-
-    while let Some(desired_val) = desired_output.pop() {
-        let desired_val = desired_val.to_string();
-
-        if iter_count == 0 {
-            for val in 0..8 {
-                let mut device = device.clone();
-
-                device.reg_a = val;
+struct Device {
+    registers: Vec<i64>,
+    program: Vec<i64>,
+}
 
-                let output = device.execute();
+impl Device {
+    fn from_str(str: &str) -> Result<Self, ParseError> {
+        let mut blocks = blocks(str);
 
-                if output == desired_val {
-                    possible_a_vals.push(val);
-                }
-            }
-        } else {
-            for val in 0..8 {
-                for possible_a in possible_a_vals.iter() {
-                    let val = possible_a << 3 | val;
+        let registers = Pattern::new("Register {}: {}")
+            .parse_lines::<(char, i64)>(blocks.next().unwrap_or_default())?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
 
-                    let mut device = device.clone();
+        let program = ints(blocks.next().unwrap_or_default())?;
 
-                    device.reg_a = val;
+        Ok(Device { registers, program })
+    }
 
-                    let output = device.execute();
+    fn machine(&self) -> Result<Machine<Instruction>, String> {
+        Machine::from_words(&self.program, &self.registers).map_err(|e| e.to_string())
+    }
+}
 
-                    if output == desired_val {
-                        next_possible_a_vals.push(val);
-                    }
-                }
-            }
+pub fn part_one(input: &str) -> Result<String, String> {
+    let device = Device::from_str(input).map_err(|e| e.to_string())?;
+    let mut machine = device.machine()?;
 
-            possible_a_vals = std::mem::take(&mut next_possible_a_vals);
-        }
+    machine.run().map_err(|e| e.to_string())?;
 
-        iter_count += 1;
-    }
+    Ok(machine
+        .output()
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
 
-    Some(possible_a_vals.into_iter().min().unwrap())
+pub fn part_two(input: &str) -> Result<u64, String> {
+    let device = Device::from_str(input).map_err(|e| e.to_string())?;
+    let mut machine = device.machine()?;
+    let program = &device.program;
+
+    // candidates which do not halt within the limit are rejected, e.g. programs that never shrink register a.
+    let max_steps = 64 * program.len();
+
+    // the program outputs one value per octal digit of register a, starting with the least significant.
+    let possible_a_vals = search_digits(8, program.len(), |a, found_digits| {
+        machine.reset(&[a as i64, device.registers[1], device.registers[2]]);
+        matches!(machine.run_limited(max_steps), Ok(Status::Halted))
+            && machine.output() == &program[program.len() - found_digits..]
+    });
+
+    possible_a_vals
+        .into_iter()
+        .next()
+        .ok_or("no value of register a makes the program output itself".into())
 }
 
 #[cfg(test)]
@@ -231,13 +170,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(String::from("4,6,3,5,6,3,5,2,1,0")));
+        let (input, _) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_one(&input);
+        assert_eq!(result, Ok(String::from("4,6,3,5,6,3,5,2,1,0")));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let (input, _) = advent_of_code::template::read_example(YEAR, DAY, "quine");
+        let result = part_two(&input);
+        assert_eq!(result, Ok(117440));
+
+        let (input, _) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        assert!(part_two(&input).is_err());
+
+        // loops forever unless register a is 0.
+        let looping = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,3,0";
+        assert!(part_two(looping).is_err());
     }
 }
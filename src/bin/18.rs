@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use advent_of_code::template::Context;
+
 advent_of_code::solution!(18);
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
@@ -82,14 +84,14 @@ struct Position {
     cost: u32,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, ctx: &Context) -> Option<u32> {
     let start = (0, 0);
-    let end = (70, 70);
+    let end = end(ctx);
     let mut grid = Grid::initialize(end.0 + 1, end.1 + 1, '.');
 
     input
         .lines()
-        .take(1024)
+        .take(ctx.param_or("bytes", 1024))
         .map(|x| x.split_once(',').unwrap())
         .map(|(x, y)| (x.parse().unwrap(), y.parse().unwrap()))
         .for_each(|pos| *grid.at_mut(pos) = '#');
@@ -97,9 +99,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     find_shortest_path_len(&mut grid, start, end)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, ctx: &Context) -> Option<String> {
     let start = (0, 0);
-    let end = (70, 70);
+    let end = end(ctx);
     let mut grid = Grid::initialize(end.0 + 1, end.1 + 1, '.');
 
     for pos in input
@@ -117,6 +119,12 @@ pub fn part_two(input: &str) -> Option<String> {
     None
 }
 
+/// The exit is in the bottom right corner, the examples use a smaller memory space than the real input.
+fn end(ctx: &Context) -> (usize, usize) {
+    let size = ctx.param_or("size", 70);
+    (size, size)
+}
+
 fn find_shortest_path_len(
     grid: &mut Grid<char>,
     start: (usize, usize),
@@ -154,13 +162,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_one(&input, &ctx);
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_two(&input, &ctx);
         assert_eq!(result, Some(String::from("6,1")));
     }
 }
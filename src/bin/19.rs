@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use advent_of_code::hash::{FastCollection, FastMap};
+use advent_of_code::memo::FxMemo;
 
 advent_of_code::solution!(19);
 
@@ -7,7 +8,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     let towels = towels.split(", ").collect::<Vec<_>>();
 
-    let mut pattern_possibility = HashMap::new();
+    let mut pattern_possibility = FastMap::new();
 
     let res = patterns
         .lines()
@@ -22,7 +23,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     let towels = towels.split(", ").collect::<Vec<_>>();
 
-    let mut pattern_possibility = HashMap::new();
+    let mut pattern_possibility = FxMemo::new();
 
     let res = patterns
         .lines()
@@ -35,7 +36,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 fn is_pattern_possible(
     pattern: &str,
     towels: &[&str],
-    pattern_possibility: &mut HashMap<String, bool>,
+    pattern_possibility: &mut FastMap<String, bool>,
 ) -> bool {
     if pattern.is_empty() {
         return true;
@@ -69,35 +70,23 @@ fn is_pattern_possible(
 fn pattern_possibility_count(
     pattern: &str,
     towels: &[&str],
-    pattern_possibility: &mut HashMap<String, u64>,
+    pattern_possibility: &mut FxMemo<String, u64>,
 ) -> u64 {
     if pattern.is_empty() {
         return 1;
     }
 
-    if let Some(res) = pattern_possibility.get(pattern) {
-        return *res;
-    }
+    pattern_possibility.get_or_compute_borrowed(pattern, |pattern_possibility| {
+        let mut total_count = 0;
 
-    let mut total_count = 0;
-
-    for i in 1..pattern.len() + 1 {
         for &towel in towels.iter() {
-            if pattern[0..i] == *towel {
-                let count = pattern_possibility_count(&pattern[i..], towels, pattern_possibility);
-
-                pattern_possibility
-                    .entry(String::from(&pattern[i..]))
-                    .or_insert(count);
-
-                total_count += count;
+            if let Some(rest) = pattern.strip_prefix(towel) {
+                total_count += pattern_possibility_count(rest, towels, pattern_possibility);
             }
         }
-    }
-
-    pattern_possibility.insert(String::from(pattern), total_count);
 
-    return total_count;
+        total_count
+    })
 }
 
 #[cfg(test)]
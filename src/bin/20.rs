@@ -1,6 +1,9 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::VecDeque;
 
-advent_of_code::solution!(20);
+use advent_of_code::hash::{FastCollection, FastMap, FastSet};
+use advent_of_code::template::Context;
+
+advent_of_code::solution!(20, parse = parse);
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 enum Direction {
@@ -122,34 +125,58 @@ struct Position {
     cost: u32,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut grid = Grid::from_str(input);
+pub struct Input {
+    grid: Grid<char>,
+    start: (usize, usize),
+    end: (usize, usize),
+    from_start: FastMap<(usize, usize), u32>,
+}
+
+pub fn parse(input: &str) -> Input {
+    let grid = Grid::from_str(input);
 
     let start = grid.find_first('S').unwrap();
     let end = grid.find_first('E').unwrap();
 
     let from_start = find_dist_from_start(&grid, start, end);
 
-    let res = find_shortcut_lenghts(&mut grid, &from_start, start, end)
-        .into_iter()
-        .filter(|&x| x >= 100)
-        .count();
-
-    Some(res as u32)
+    Input {
+        grid,
+        start,
+        end,
+        from_start,
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut grid = Grid::from_str(input);
+pub fn part_one(input: &Input, ctx: &Context) -> Option<u32> {
+    // the examples only have shortcuts that save a few picoseconds.
+    let threshold = ctx.param_or("threshold", 100);
 
-    let start = grid.find_first('S').unwrap();
-    let end = grid.find_first('E').unwrap();
+    let res = find_shortcut_lenghts(
+        &mut input.grid.clone(),
+        &input.from_start,
+        input.start,
+        input.end,
+    )
+    .into_iter()
+    .filter(|&x| x >= threshold)
+    .count();
 
-    let from_start = find_dist_from_start(&grid, start, end);
+    Some(res as u32)
+}
+
+pub fn part_two(input: &Input, ctx: &Context) -> Option<u32> {
+    let threshold = ctx.param_or("threshold", 100);
 
-    let res = find_20ps_shortcut_lenghts(&mut grid, &from_start, start, end)
-        .into_iter()
-        .filter(|&x| x >= 100)
-        .count();
+    let res = find_20ps_shortcut_lenghts(
+        &mut input.grid.clone(),
+        &input.from_start,
+        input.start,
+        input.end,
+    )
+    .into_iter()
+    .filter(|&x| x >= threshold)
+    .count();
 
     Some(res as u32)
 }
@@ -158,8 +185,8 @@ fn find_dist_from_start(
     grid: &Grid<char>,
     start: (usize, usize),
     end: (usize, usize),
-) -> HashMap<(usize, usize), u32> {
-    let mut from_start = HashMap::new();
+) -> FastMap<(usize, usize), u32> {
+    let mut from_start = FastMap::new();
     let mut current = start;
     let mut dist = 0;
 
@@ -182,7 +209,7 @@ fn find_dist_from_start(
 
 fn find_shortcut_lenghts(
     grid: &mut Grid<char>,
-    from_start: &HashMap<(usize, usize), u32>,
+    from_start: &FastMap<(usize, usize), u32>,
     start: (usize, usize),
     end: (usize, usize),
 ) -> Vec<u32> {
@@ -229,7 +256,7 @@ fn find_shortcut_lenghts(
 
 fn find_20ps_shortcut_lenghts(
     grid: &mut Grid<char>,
-    from_start: &HashMap<(usize, usize), u32>,
+    from_start: &FastMap<(usize, usize), u32>,
     start: (usize, usize),
     end: (usize, usize),
 ) -> Vec<u32> {
@@ -268,10 +295,10 @@ fn find_shortcuts_from(
     grid: &Grid<char>,
     start: (usize, usize),
     max_dist: u32,
-) -> HashSet<Position> {
-    let mut res = HashSet::new();
+) -> FastSet<Position> {
+    let mut res = FastSet::new();
     let mut queue = VecDeque::new();
-    let mut queued = HashSet::new();
+    let mut queued = FastSet::new();
 
     queue.push_back(Position {
         pos: start,
@@ -317,13 +344,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_one(&parse(&input), &ctx);
+        assert_eq!(result, Some(1));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let (input, ctx) = advent_of_code::template::read_example(YEAR, DAY, "default");
+        let result = part_two(&parse(&input), &ctx);
+        assert_eq!(result, Some(285));
     }
 }
@@ -0,0 +1,389 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign};
+
+use crate::grid::Point;
+
+/// One of the four orthogonal directions on a grid.
+///
+/// Variants are ordered clockwise starting from [`Direction::Up`], which is also the order of [`Direction::ALL`].
+/// Directions are screen-oriented: [`Direction::Up`] decreases `y`.
+///
+/// ```
+/// # use advent_of_code::compass::Direction;
+/// let dir = Direction::try_from('^').unwrap();
+/// assert_eq!(dir.turn_right(), Direction::Right);
+/// assert_eq!(dir.turn_around(), Direction::Down)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Iterates over all directions, clockwise starting from [`Direction::Up`].
+    pub fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the direction after a quarter turn clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Returns the direction after a quarter turn counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+
+    /// Returns the direction after a half turn.
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Returns the direction pointing the other way. Same as [`Direction::turn_around`].
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.turn_around()
+    }
+
+    /// Returns the two directions perpendicular to this one.
+    pub fn perpendicular(self) -> [Self; 2] {
+        [self.turn_left(), self.turn_right()]
+    }
+
+    /// Returns the minimum number of quarter turns (0 to 2) needed to face `other`.
+    pub fn quarter_turns_to(self, other: Self) -> u8 {
+        let clockwise = (other.index() + 4 - self.index()) % 4;
+        clockwise.min(4 - clockwise)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Returns the offset of a single step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Right => Point::new(1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// Returns the arrow character (`^>v<`) for this direction.
+    pub fn to_arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
+    fn index(self) -> u8 {
+        self as u8
+    }
+
+    fn rotate(self, quarter_turns: u8) -> Self {
+        Self::ALL[usize::from((self.index() + quarter_turns) % 4)]
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_arrow())
+    }
+}
+
+/// Parses arrows (`^>v<`), relative letters (`UDLR`) and compass letters (`NESW`).
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromCharError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'U' | 'N' => Ok(Self::Up),
+            '>' | 'R' | 'E' => Ok(Self::Right),
+            'v' | 'D' | 'S' => Ok(Self::Down),
+            '<' | 'L' | 'W' => Ok(Self::Left),
+            c => Err(DirectionFromCharError(c)),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Direction`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirectionFromCharError(pub char);
+
+impl Error for DirectionFromCharError {}
+
+impl Display for DirectionFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expecting one of `^>v<`, `UDLR` or `NESW`, found `{}`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the eight directions on a grid, including diagonals.
+///
+/// Variants are ordered clockwise starting from [`ExtendedDirection::Up`], which is also the order of [`ExtendedDirection::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExtendedDirection {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl ExtendedDirection {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Iterates over all directions, clockwise starting from [`ExtendedDirection::Up`].
+    pub fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Combines two perpendicular directions into the diagonal between them, e.g. up and right into up-right.
+    /// Returns [`None`] for parallel directions.
+    pub fn combine(a: Direction, b: Direction) -> Option<Self> {
+        match (a, b) {
+            (Direction::Up, Direction::Right) | (Direction::Right, Direction::Up) => {
+                Some(Self::UpRight)
+            }
+            (Direction::Down, Direction::Right) | (Direction::Right, Direction::Down) => {
+                Some(Self::DownRight)
+            }
+            (Direction::Down, Direction::Left) | (Direction::Left, Direction::Down) => {
+                Some(Self::DownLeft)
+            }
+            (Direction::Up, Direction::Left) | (Direction::Left, Direction::Up) => {
+                Some(Self::UpLeft)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the direction after an eighth turn clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Returns the direction after an eighth turn counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// Returns the direction after a half turn.
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Returns the direction pointing the other way. Same as [`ExtendedDirection::turn_around`].
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.turn_around()
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Returns the orthogonal [`Direction`] if this is not a diagonal.
+    pub fn to_orthogonal(self) -> Option<Direction> {
+        match self {
+            Self::Up => Some(Direction::Up),
+            Self::Right => Some(Direction::Right),
+            Self::Down => Some(Direction::Down),
+            Self::Left => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Returns the offset of a single step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::UpRight => Point::new(1, -1),
+            Self::Right => Point::new(1, 0),
+            Self::DownRight => Point::new(1, 1),
+            Self::Down => Point::new(0, 1),
+            Self::DownLeft => Point::new(-1, 1),
+            Self::Left => Point::new(-1, 0),
+            Self::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn index(self) -> u8 {
+        self as u8
+    }
+
+    fn rotate(self, eighth_turns: u8) -> Self {
+        Self::ALL[usize::from((self.index() + eighth_turns) % 8)]
+    }
+}
+
+impl From<Direction> for ExtendedDirection {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<ExtendedDirection> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: ExtendedDirection) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign<ExtendedDirection> for Point {
+    fn add_assign(&mut self, rhs: ExtendedDirection) {
+        *self = *self + rhs;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    mod direction {
+        use crate::{compass::Direction, grid::Point};
+
+        #[test]
+        fn turns() {
+            assert_eq!(Direction::Up.turn_right(), Direction::Right);
+            assert_eq!(Direction::Up.turn_left(), Direction::Left);
+            assert_eq!(Direction::Left.turn_right(), Direction::Up);
+            assert_eq!(Direction::Right.turn_around(), Direction::Left);
+            assert_eq!(Direction::Down.opposite(), Direction::Up);
+            assert_eq!(
+                Direction::Up.perpendicular(),
+                [Direction::Left, Direction::Right]
+            );
+        }
+
+        #[test]
+        fn counts_quarter_turns() {
+            assert_eq!(Direction::Up.quarter_turns_to(Direction::Up), 0);
+            assert_eq!(Direction::Up.quarter_turns_to(Direction::Left), 1);
+            assert_eq!(Direction::Left.quarter_turns_to(Direction::Up), 1);
+            assert_eq!(Direction::Right.quarter_turns_to(Direction::Left), 2);
+        }
+
+        #[test]
+        fn sums_to_zero() {
+            let sum = Direction::all().fold(Point::ORIGIN, |acc, dir| acc + dir);
+            assert_eq!(sum, Point::ORIGIN);
+            assert_eq!(Point::new(2, 2) + Direction::Up, Point::new(2, 1));
+        }
+
+        #[test]
+        fn parses_chars() {
+            for (chars, dir) in ["^UN", ">RE", "vDS", "<LW"].iter().zip(Direction::ALL) {
+                for c in chars.chars() {
+                    assert_eq!(Direction::try_from(c), Ok(dir));
+                }
+                assert_eq!(Direction::try_from(dir.to_arrow()), Ok(dir));
+            }
+        }
+
+        #[test]
+        fn errors_for_invalid_chars() {
+            assert!(Direction::try_from('x').is_err());
+        }
+    }
+
+    mod extended_direction {
+        use crate::compass::{Direction, ExtendedDirection};
+
+        #[test]
+        fn turns() {
+            assert_eq!(
+                ExtendedDirection::Up.turn_right(),
+                ExtendedDirection::UpRight
+            );
+            assert_eq!(ExtendedDirection::Up.turn_left(), ExtendedDirection::UpLeft);
+            assert_eq!(
+                ExtendedDirection::DownLeft.turn_around(),
+                ExtendedDirection::UpRight
+            );
+        }
+
+        #[test]
+        fn combines_perpendicular_directions() {
+            assert_eq!(
+                ExtendedDirection::combine(Direction::Left, Direction::Down),
+                Some(ExtendedDirection::DownLeft)
+            );
+            assert_eq!(
+                ExtendedDirection::combine(Direction::Left, Direction::Right),
+                None
+            );
+        }
+
+        #[test]
+        fn deltas_match_orthogonal_directions() {
+            for dir in Direction::all() {
+                let extended = ExtendedDirection::from(dir);
+                assert_eq!(extended.delta(), dir.delta());
+                assert_eq!(extended.to_orthogonal(), Some(dir));
+                assert!(!extended.is_diagonal());
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+use super::{point_at, Point};
+
+/// A fixed-size set of points in `0..width` x `0..height`, stored as one bit per cell.
+///
+/// Much faster and smaller than a hash set of points for marking visited cells of a [`Grid`](super::Grid).
+///
+/// # Display
+/// This value displays like a `Grid<char>` with `#` for contained points and `.` otherwise.
+///
+/// ```
+/// # use advent_of_code::grid::{BitGrid, Point};
+/// let mut visited = BitGrid::new(3, 2);
+/// assert!(visited.insert(Point::new(1, 0)));
+/// assert!(!visited.insert(Point::new(1, 0)));
+/// assert_eq!(visited.to_string(), ".#.\n...");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    len: usize,
+}
+
+impl BitGrid {
+    /// Creates an empty `width` x `height` grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            words: vec![0; (width * height).div_ceil(64)],
+            width,
+            height,
+            len: 0,
+        }
+    }
+
+    /// Creates a grid containing every point for which `f` returns `true`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> bool) -> Self {
+        let mut grid = Self::new(width, height);
+        for idx in 0..width * height {
+            if f(point_at(idx, width)) {
+                grid.set_bit(idx);
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the point lies within the grid.
+    pub fn in_bounds(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Number of contained points.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the point is contained, `false` for points out of bounds.
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point)
+            .is_some_and(|idx| self.words[idx / 64] & (1 << (idx % 64)) != 0)
+    }
+
+    /// Adds a point, returns `true` if it was not contained before.
+    ///
+    /// # Panics
+    /// Panics if the point is out of bounds.
+    pub fn insert(&mut self, point: Point) -> bool {
+        let idx = self
+            .index_of(point)
+            .unwrap_or_else(|| panic!("point {point} is out of bounds"));
+        self.set_bit(idx)
+    }
+
+    fn set_bit(&mut self, idx: usize) -> bool {
+        let mask = 1 << (idx % 64);
+        let word = &mut self.words[idx / 64];
+        let is_new = *word & mask == 0;
+
+        *word |= mask;
+        self.len += usize::from(is_new);
+        is_new
+    }
+
+    /// Removes a point, returns `true` if it was contained. Points out of bounds are never contained.
+    pub fn remove(&mut self, point: Point) -> bool {
+        let Some(idx) = self.index_of(point) else {
+            return false;
+        };
+
+        let mask = 1 << (idx % 64);
+        let word = &mut self.words[idx / 64];
+        let was_contained = *word & mask != 0;
+
+        *word &= !mask;
+        self.len -= usize::from(was_contained);
+        was_contained
+    }
+
+    /// Removes all points.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    /// Iterates over the contained points in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(point_at(i * 64 + bit, self.width))
+            })
+        })
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                #[allow(clippy::cast_possible_wrap)]
+                let c = if self.contains(Point::new(x as isize, y as isize)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BitGrid;
+    use crate::grid::Point;
+
+    #[test]
+    fn inserts_and_removes_points() {
+        let mut grid = BitGrid::new(10, 10);
+        assert!(grid.is_empty());
+
+        assert!(grid.insert(Point::new(9, 9)));
+        assert!(grid.insert(Point::new(3, 6)));
+        assert!(!grid.insert(Point::new(3, 6)));
+        assert_eq!(grid.len(), 2);
+
+        assert!(grid.contains(Point::new(9, 9)));
+        assert!(!grid.contains(Point::new(9, 8)));
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert!(!grid.contains(Point::new(10, 0)));
+
+        assert!(grid.remove(Point::new(9, 9)));
+        assert!(!grid.remove(Point::new(9, 9)));
+        assert!(!grid.remove(Point::new(20, 20)));
+        assert_eq!(grid.len(), 1);
+
+        grid.clear();
+        assert!(grid.is_empty());
+        assert!(!grid.contains(Point::new(3, 6)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn panics_when_inserting_out_of_bounds() {
+        BitGrid::new(2, 2).insert(Point::new(2, 0));
+    }
+
+    #[test]
+    fn iterates_in_row_major_order() {
+        let grid = BitGrid::from_fn(20, 5, |p| (p.x + p.y) % 7 == 0);
+        let points: Vec<Point> = grid.iter().collect();
+
+        assert_eq!(points.len(), grid.len());
+        assert_eq!(points[0], Point::new(0, 0));
+        assert_eq!(points[1], Point::new(7, 0));
+        assert!(points
+            .windows(2)
+            .all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+        assert!(points.iter().all(|&p| grid.contains(p)));
+    }
+
+    #[test]
+    fn displays_as_grid() {
+        let grid = BitGrid::from_fn(3, 2, |p| p.x == p.y);
+        assert_eq!(grid.to_string(), "#..\n.#.");
+    }
+}
@@ -0,0 +1,635 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::compass::{Direction, ExtendedDirection};
+
+pub use bit_grid::*;
+pub use point::*;
+pub use point_set::*;
+
+mod bit_grid;
+mod point;
+mod point_set;
+
+/// A dense, row-major 2D grid.
+///
+/// Cells are addressed with [`Point`]s. Indexing (`grid[point]`) panics when out of bounds,
+/// [`Grid::get`] returns [`None`] instead and [`Grid::get_wrapping`] treats the grid as a torus.
+///
+/// # Display
+/// A grid of [`Display`] cells renders one line per row.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Point};
+/// let grid: Grid<char> = "#.\n.#".parse().unwrap();
+/// assert_eq!(grid[Point::new(1, 1)], '#');
+/// assert_eq!(grid.to_string(), "#.\n.#")
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid by calling `f` with the position of every cell, in row-major order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let data = (0..width * height)
+            .map(|idx| f(point_at(idx, width)))
+            .collect();
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from row-major `data`, returns [`None`] if its length does not match the dimensions.
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Option<Self> {
+        if data.len() != width * height {
+            return None;
+        }
+
+        Some(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a block of text into a grid, converting every character with `f`.
+    ///
+    /// Trailing empty lines are ignored, all other lines must have the same length.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let lines: Vec<&str> = input.trim_end_matches(['\n', '\r']).lines().collect();
+
+        let width = match lines.first() {
+            Some(line) if !line.is_empty() => line.chars().count(),
+            _ => return Err(ParseGridError::Empty),
+        };
+
+        let mut data = Vec::with_capacity(width * lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            let len_before = data.len();
+            data.extend(line.chars().map(&mut f));
+
+            let found = data.len() - len_before;
+            if found != width {
+                return Err(ParseGridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height: lines.len(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the point lies within the grid.
+    pub fn in_bounds(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    /// Converts a point into an index into the underlying row-major storage.
+    pub fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an index into the underlying row-major storage into a point.
+    pub fn point_of(&self, idx: usize) -> Point {
+        point_at(idx, self.width)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.data[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.data[idx])
+    }
+
+    /// Returns the cell at `point`, wrapping around the edges as if the grid were a torus.
+    pub fn get_wrapping(&self, point: Point) -> &T {
+        &self[point.wrap(self.width, self.height)]
+    }
+
+    /// Returns the cell at `point`, wrapping around the edges as if the grid were a torus.
+    pub fn get_wrapping_mut(&mut self, point: Point) -> &mut T {
+        let point = point.wrap(self.width, self.height);
+        &mut self[point]
+    }
+
+    /// Iterates over all cells with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(idx, val)| (point_at(idx, width), val))
+    }
+
+    /// Iterates mutably over all cells with their positions, in row-major order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(idx, val)| (point_at(idx, width), val))
+    }
+
+    /// Iterates over all positions of the grid, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.data.len()).map(move |idx| point_at(idx, width))
+    }
+
+    /// Iterates over the positions of all cells equal to `value`.
+    pub fn find<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, val)| *val == value)
+            .map(|(point, _)| point)
+    }
+
+    /// Returns the position of the first cell equal to `value`.
+    pub fn find_first(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find(value).next()
+    }
+
+    /// Creates a new grid of the same dimensions by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns row `y` as a slice.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over all rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// Iterates over column `x`, top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over all columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Walks from `start` in steps of `step` until leaving the grid, yielding every visited cell.
+    /// `start` itself is included if it is within bounds.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |point| Some(*point + step))
+            .map_while(|point| self.get(point).map(|val| (point, val)))
+    }
+
+    /// Iterates over all diagonals running from top-left to bottom-right.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        self.diagonal_starts(0)
+            .map(|start| self.ray(start, Point::new(1, 1)))
+    }
+
+    /// Iterates over all diagonals running from top-right to bottom-left.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        #[allow(clippy::cast_possible_wrap)]
+        let last_column = self.width as isize - 1;
+        self.diagonal_starts(last_column)
+            .map(|start| self.ray(start, Point::new(-1, 1)))
+    }
+
+    /// Every diagonal starts either in the top row or in the column `x`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn diagonal_starts(&self, x: isize) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let top = (0..width).map(|x| Point::new(x, 0));
+        let side = (1..height).map(move |y| Point::new(x, y));
+        top.chain(side)
+    }
+
+    /// Returns the position one step from `point` in direction `dir`, if it is within bounds.
+    pub fn step(&self, point: Point, dir: Direction) -> Option<Point> {
+        Some(point + dir).filter(|next| self.in_bounds(*next))
+    }
+
+    /// Iterates over the in-bounds orthogonal neighbours of `point`, clockwise starting from the top.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors_with_dirs(point).map(|(next, _)| next)
+    }
+
+    /// Iterates over the in-bounds orthogonal neighbours of `point` along with the direction leading to them.
+    pub fn neighbors_with_dirs(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = (Point, Direction)> + '_ {
+        Direction::all()
+            .map(move |dir| (point + dir, dir))
+            .filter(|(next, _)| self.in_bounds(*next))
+    }
+
+    /// Iterates over all in-bounds neighbours of `point` including diagonals, clockwise starting from the top.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors8_with_dirs(point).map(|(next, _)| next)
+    }
+
+    /// Iterates over all in-bounds neighbours of `point` including diagonals, along with the direction leading to them.
+    pub fn neighbors8_with_dirs(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = (Point, ExtendedDirection)> + '_ {
+        ExtendedDirection::all()
+            .map(move |dir| (point + dir, dir))
+            .filter(|(next, _)| self.in_bounds(*next))
+    }
+
+    /// Returns a grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    /// Returns the grid rotated by 90 degrees clockwise.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_row = self.height as isize - 1;
+        self.remap(self.height, self.width, |p| Point::new(p.y, last_row - p.x))
+    }
+
+    /// Returns the grid rotated by 90 degrees counter-clockwise.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_column = self.width as isize - 1;
+        self.remap(self.height, self.width, |p| {
+            Point::new(last_column - p.y, p.x)
+        })
+    }
+
+    /// Returns the grid mirrored along its vertical axis.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_column = self.width as isize - 1;
+        self.remap(self.width, self.height, |p| {
+            Point::new(last_column - p.x, p.y)
+        })
+    }
+
+    /// Returns the grid mirrored along its horizontal axis.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_row = self.height as isize - 1;
+        self.remap(self.width, self.height, |p| Point::new(p.x, last_row - p.y))
+    }
+
+    /// Builds a new grid where every cell is copied from the source position returned by `source`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(Point) -> Point) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(width, height, |point| self[source(point)].clone())
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn point_at(idx: usize, width: usize) -> Point {
+    Point::new((idx % width) as isize, (idx / width) as isize)
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        match self.index_of(point) {
+            Some(idx) => &self.data[idx],
+            None => panic!("point {point} is out of bounds"),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        match self.index_of(point) {
+            Some(idx) => &mut self.data[idx],
+            None => panic!("point {point} is out of bounds"),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => f.write_str("expecting at least one non-empty row"),
+            ParseGridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expecting row {row} to have {expected} cells, found {found}"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError, Point};
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    mod parsing {
+        use super::{get_mock_grid, Grid, ParseGridError, Point};
+
+        #[test]
+        fn parses_rows() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.width(), 3);
+            assert_eq!(grid.height(), 2);
+            assert_eq!(grid[Point::new(2, 1)], 'f');
+        }
+
+        #[test]
+        fn ignores_trailing_newlines() {
+            let grid: Grid<char> = "ab\r\ncd\r\n\n".parse().unwrap();
+            assert_eq!(grid.height(), 2);
+            assert_eq!(grid.row(1), &['c', 'd']);
+        }
+
+        #[test]
+        fn parses_with_conversion() {
+            let grid = Grid::parse_with("09\n12", |c| c.to_digit(10).unwrap()).unwrap();
+            assert_eq!(grid[Point::new(1, 0)], 9);
+        }
+
+        #[test]
+        fn errors_for_empty_input() {
+            assert_eq!("\n".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        }
+
+        #[test]
+        fn errors_for_ragged_rows() {
+            assert_eq!(
+                "abc\nde".parse::<Grid<char>>(),
+                Err(ParseGridError::RaggedRow {
+                    row: 1,
+                    expected: 3,
+                    found: 2
+                })
+            );
+        }
+
+        #[test]
+        fn displays_rows() {
+            assert_eq!(get_mock_grid().to_string(), "abc\ndef");
+        }
+    }
+
+    mod access {
+        use super::{get_mock_grid, Point};
+
+        #[test]
+        fn checks_bounds() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.get(Point::new(0, 0)), Some(&'a'));
+            assert_eq!(grid.get(Point::new(3, 0)), None);
+            assert_eq!(grid.get(Point::new(0, -1)), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_when_indexing_out_of_bounds() {
+            let _ = get_mock_grid()[Point::new(-1, 0)];
+        }
+
+        #[test]
+        fn wraps_around_edges() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.get_wrapping(Point::new(-1, 0)), &'c');
+            assert_eq!(grid.get_wrapping(Point::new(4, 3)), &'e');
+        }
+
+        #[test]
+        fn finds_values() {
+            let mut grid = get_mock_grid();
+            *grid.get_mut(Point::new(2, 1)).unwrap() = 'a';
+            assert_eq!(grid.find_first(&'a'), Some(Point::new(0, 0)));
+            assert_eq!(grid.find(&'a').count(), 2);
+            assert_eq!(grid.find_first(&'z'), None);
+        }
+    }
+
+    mod iteration {
+        use super::{get_mock_grid, Point};
+        use crate::compass::Direction;
+
+        #[test]
+        fn iterates_rows_and_columns() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.rows().count(), 2);
+            assert_eq!(grid.column(1).collect::<String>(), "be");
+            assert_eq!(
+                grid.columns()
+                    .map(|c| c.collect::<String>())
+                    .collect::<Vec<_>>(),
+                ["ad", "be", "cf"]
+            );
+        }
+
+        #[test]
+        fn iterates_diagonals() {
+            let grid = get_mock_grid();
+            let diagonals: Vec<String> = grid
+                .diagonals()
+                .map(|d| d.map(|(_, c)| c).collect())
+                .collect();
+            assert_eq!(diagonals, ["ae", "bf", "c", "d"]);
+
+            let anti_diagonals: Vec<String> = grid
+                .anti_diagonals()
+                .map(|d| d.map(|(_, c)| c).collect())
+                .collect();
+            assert_eq!(anti_diagonals, ["a", "bd", "ce", "f"]);
+        }
+
+        #[test]
+        fn casts_rays() {
+            let grid = get_mock_grid();
+            let ray: String = grid
+                .ray(Point::new(2, 0), Point::new(-1, 0))
+                .map(|(_, c)| c)
+                .collect();
+            assert_eq!(ray, "cba");
+        }
+
+        #[test]
+        fn yields_neighbors_in_bounds() {
+            let grid = get_mock_grid();
+            assert_eq!(
+                grid.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+                [Point::new(1, 0), Point::new(0, 1)]
+            );
+            assert_eq!(grid.neighbors8(Point::new(1, 0)).count(), 5);
+            assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 5);
+        }
+
+        #[test]
+        fn yields_neighbors_with_directions() {
+            let grid = get_mock_grid();
+            assert_eq!(
+                grid.neighbors_with_dirs(Point::new(2, 1))
+                    .collect::<Vec<_>>(),
+                [
+                    (Point::new(2, 0), Direction::Up),
+                    (Point::new(1, 1), Direction::Left)
+                ]
+            );
+            assert_eq!(grid.step(Point::new(2, 1), Direction::Down), None);
+            assert_eq!(
+                grid.step(Point::new(2, 1), Direction::Left),
+                Some(Point::new(1, 1))
+            );
+        }
+    }
+
+    mod transformation {
+        use super::get_mock_grid;
+
+        #[test]
+        fn transposes() {
+            assert_eq!(get_mock_grid().transpose().to_string(), "ad\nbe\ncf");
+        }
+
+        #[test]
+        fn rotates() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+            assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+            assert_eq!(grid.rotate_right().rotate_left(), grid);
+        }
+
+        #[test]
+        fn flips() {
+            let grid = get_mock_grid();
+            assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+            assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        }
+
+        #[test]
+        fn maps_cells() {
+            let grid = get_mock_grid().map(|c| c.is_ascii_uppercase());
+            assert!(grid.iter().all(|(_, v)| !v));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed 2D coordinate on a [`Grid`](super::Grid).
+///
+/// `x` grows to the right and `y` grows downwards, matching the order in which puzzle inputs are read.
+/// Coordinates are signed so that stepping off the edge of a grid can be expressed and then bounds-checked.
+///
+/// # Display
+/// This value displays as `x,y`.
+///
+/// ```
+/// # use advent_of_code::grid::Point;
+/// let point = Point::new(3, -1);
+/// assert_eq!(point.to_string(), "3,-1")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    /// Creates a [`Point`] from its coordinates.
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the manhattan (taxicab) distance between two points.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Returns the chebyshev (king move) distance between two points.
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Returns the point with both coordinates wrapped into `0..width` and `0..height`.
+    ///
+    /// # Panics
+    /// Panics if `width` or `height` is 0.
+    pub fn wrap(self, width: usize, height: usize) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "can not wrap {self} into an empty {width}x{height} area"
+        );
+
+        #[allow(clippy::cast_possible_wrap)]
+        Self::new(
+            self.x.rem_euclid(width as isize),
+            self.y.rem_euclid(height as isize),
+        )
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    #[allow(clippy::cast_possible_wrap)]
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Point;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn wraps_negative_coordinates() {
+        assert_eq!(Point::new(-1, 7).wrap(5, 5), Point::new(4, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty 0x5 area")]
+    fn rejects_wrapping_into_empty_areas() {
+        let _ = Point::new(1, 1).wrap(0, 5);
+    }
+}
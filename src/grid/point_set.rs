@@ -0,0 +1,193 @@
+use super::{BitGrid, Point};
+
+/// A set of points with arbitrary coordinates, stored as a [`BitGrid`] over their bounding box.
+///
+/// The bounding box grows as points are inserted, so the set is best suited for points that are close together,
+/// e.g. the cells visited by a simulation that leaves the input grid.
+///
+/// ```
+/// # use advent_of_code::grid::{Point, PointSet};
+/// let mut set = PointSet::new();
+/// set.insert(Point::new(-5, 3));
+/// set.insert(Point::new(100, -20));
+/// assert!(set.contains(Point::new(-5, 3)));
+/// assert_eq!(set.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PointSet {
+    /// The point stored at `(0, 0)` of `bits`.
+    origin: Point,
+    bits: BitGrid,
+}
+
+impl Default for PointSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PointSet {
+    pub fn new() -> Self {
+        Self {
+            origin: Point::ORIGIN,
+            bits: BitGrid::new(0, 0),
+        }
+    }
+
+    /// Creates an empty set that can hold points in `0..width` x `0..height` without growing.
+    pub fn with_bounds(width: usize, height: usize) -> Self {
+        Self {
+            origin: Point::ORIGIN,
+            bits: BitGrid::new(width, height),
+        }
+    }
+
+    /// Number of contained points.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.bits.contains(point - self.origin)
+    }
+
+    /// Adds a point, returns `true` if it was not contained before.
+    pub fn insert(&mut self, point: Point) -> bool {
+        if !self.bits.in_bounds(point - self.origin) {
+            self.grow_to(point);
+        }
+        self.bits.insert(point - self.origin)
+    }
+
+    /// Removes a point, returns `true` if it was contained.
+    pub fn remove(&mut self, point: Point) -> bool {
+        self.bits.remove(point - self.origin)
+    }
+
+    /// Removes all points, keeping the allocated bounding box.
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Iterates over the contained points, ordered by `y`, then `x`.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.bits.iter().map(|point| point + self.origin)
+    }
+
+    /// Grows the bounding box to include `point`, at least doubling its size to amortize copying.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    fn grow_to(&mut self, point: Point) {
+        let (width, height) = (self.bits.width() as isize, self.bits.height() as isize);
+        let end = self.origin + Point::new(width, height);
+
+        let min = Point::new(
+            if point.x < self.origin.x {
+                point.x.min(self.origin.x - width.max(8))
+            } else {
+                self.origin.x
+            },
+            if point.y < self.origin.y {
+                point.y.min(self.origin.y - height.max(8))
+            } else {
+                self.origin.y
+            },
+        );
+        let max = Point::new(
+            if point.x >= end.x {
+                (point.x + 1).max(end.x + width.max(8))
+            } else {
+                end.x
+            },
+            if point.y >= end.y {
+                (point.y + 1).max(end.y + height.max(8))
+            } else {
+                end.y
+            },
+        );
+
+        let mut bits = BitGrid::new((max.x - min.x) as usize, (max.y - min.y) as usize);
+        for p in self.iter() {
+            bits.insert(p - min);
+        }
+
+        self.origin = min;
+        self.bits = bits;
+    }
+}
+
+impl Extend<Point> for PointSet {
+    fn extend<I: IntoIterator<Item = Point>>(&mut self, iter: I) {
+        for point in iter {
+            self.insert(point);
+        }
+    }
+}
+
+impl FromIterator<Point> for PointSet {
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PointSet;
+    use crate::grid::Point;
+
+    #[test]
+    fn grows_in_all_directions() {
+        let points = [
+            Point::new(0, 0),
+            Point::new(-3, 2),
+            Point::new(50, -40),
+            Point::new(-100, 100),
+            Point::new(7, 7),
+        ];
+        let mut set = PointSet::new();
+
+        for (i, &point) in points.iter().enumerate() {
+            assert!(set.insert(point));
+            assert!(!set.insert(point));
+            assert_eq!(set.len(), i + 1);
+            assert!(points[..=i].iter().all(|&p| set.contains(p)));
+        }
+
+        assert!(!set.contains(Point::new(1, 1)));
+        assert!(!set.contains(Point::new(1000, 1000)));
+    }
+
+    #[test]
+    fn removes_and_iterates() {
+        let mut set: PointSet = [Point::new(2, 1), Point::new(-1, 1), Point::new(0, -4)]
+            .into_iter()
+            .collect();
+
+        assert!(set.remove(Point::new(2, 1)));
+        assert!(!set.remove(Point::new(2, 1)));
+        assert!(!set.remove(Point::new(500, 1)));
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Point::new(0, -4), Point::new(-1, 1)]
+        );
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn starts_with_bounds() {
+        let mut set = PointSet::with_bounds(4, 4);
+        set.insert(Point::new(3, 3));
+        assert!(set.contains(Point::new(3, 3)));
+        assert_eq!(set.len(), 1);
+    }
+}
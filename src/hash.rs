@@ -0,0 +1,163 @@
+/// A fast, non-cryptographic hasher for hash maps and sets.
+///
+/// [`FxHasher`] is the hasher used by the Rust compiler: it mixes one machine word at a time with a rotate, xor and
+/// multiply. It is much faster than the default SipHash for the small keys of puzzles (integers, points, short
+/// strings), but gives no protection against adversarial inputs.
+///
+/// Solutions can switch from the std collections by replacing `HashMap` with [`FastMap`] and `HashSet` with
+/// [`FastSet`]. With [`FastCollection`] in scope, `FastMap::new()` and `FastMap::with_capacity(n)` work as usual:
+///
+/// ```
+/// # use advent_of_code::hash::{FastCollection, FastMap};
+/// let mut counts: FastMap<char, u32> = FastMap::new();
+/// *counts.entry('a').or_default() += 1;
+/// ```
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// The hasher of the Rust compiler, see the [module docs](self).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds [`FxHasher`]s, e.g. for `HashMap::with_hasher`.
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// A [`HashMap`] using [`FxHasher`]. Create it with `FxHashMap::default()`.
+pub type FxHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A [`HashSet`] using [`FxHasher`]. Create it with `FxHashSet::default()`.
+pub type FxHashSet<T> = HashSet<T, FxBuildHasher>;
+
+/// The hash map of choice for solutions, currently an [`FxHashMap`].
+pub type FastMap<K, V> = FxHashMap<K, V>;
+
+/// The hash set of choice for solutions, currently an [`FxHashSet`].
+pub type FastSet<T> = FxHashSet<T>;
+
+/// Constructors of the std collections for collections with a default hasher like [`FastMap`] and [`FastSet`].
+pub trait FastCollection {
+    fn new() -> Self;
+    fn with_capacity(capacity: usize) -> Self;
+}
+
+impl<K: Hash + Eq, V> FastCollection for FastMap<K, V> {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, FxBuildHasher::default())
+    }
+}
+
+impl<T: Hash + Eq> FastCollection for FastSet<T> {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, FxBuildHasher::default())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{FastCollection, FastMap, FastSet, FxBuildHasher, FxHashMap, FxHashSet};
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn hashes_deterministically() {
+        let hasher = FxBuildHasher::default();
+
+        assert_eq!(hasher.hash_one((1, 2)), hasher.hash_one((1, 2)));
+        assert_ne!(hasher.hash_one((1, 2)), hasher.hash_one((2, 1)));
+        assert_ne!(hasher.hash_one("ab"), hasher.hash_one("ba"));
+        assert_ne!(
+            hasher.hash_one("a long string of bytes"),
+            hasher.hash_one("a long string of bytez")
+        );
+    }
+
+    #[test]
+    fn works_as_map_and_set() {
+        let mut map = FxHashMap::default();
+        map.insert("a", 1);
+        *map.entry("a").or_insert(0) += 1;
+        assert_eq!(map["a"], 2);
+
+        let set: FxHashSet<(i32, i32)> = (0..100).map(|i| (i, -i)).collect();
+        assert_eq!(set.len(), 100);
+        assert!(set.contains(&(42, -42)));
+    }
+
+    #[test]
+    fn constructs_fast_collections() {
+        let mut map: FastMap<u32, u32> = FastMap::with_capacity(10);
+        assert!(map.capacity() >= 10);
+        map.insert(1, 2);
+
+        let mut set = FastSet::new();
+        set.insert("a");
+        assert!(set.contains("a"));
+    }
+}
@@ -1,3 +1,13 @@
+pub mod compass;
+pub mod grid;
+pub mod hash;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod search;
 pub mod template;
+pub mod visualize;
+pub mod vm;
 
 // Use this file to add helper functions and additional modules.
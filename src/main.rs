@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, extract, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,34 +9,63 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::solve::VisualizeOptions;
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Option<Year>,
         },
         Read {
             day: Day,
+            year: Option<Year>,
+        },
+        Extract {
+            day: Day,
+            year: Option<Year>,
+            list: bool,
+            block: Option<usize>,
+            overwrite: bool,
         },
         Scaffold {
             day: Day,
+            year: Option<Year>,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
+            year: Option<Year>,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            examples: bool,
+            watch: bool,
+            visualize: VisualizeOptions,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            jobs: usize,
         },
         Time {
             all: bool,
             day: Option<Day>,
+            year: Option<Year>,
             store: bool,
+            budget: Option<u64>,
+            compare: bool,
+            baseline: Option<String>,
+            jobs: usize,
+        },
+        Verify {
+            day: Option<Day>,
+            year: Option<Year>,
+            release: bool,
+            jobs: usize,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,34 +76,71 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let budget = args.opt_value_from_str("--budget")?;
+                let compare = args.contains("--compare");
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let year = args.opt_value_from_str("--year")?;
 
                 AppArguments::Time {
                     all,
+                    year,
                     day: args.opt_free_from_str()?,
                     store,
+                    budget,
+                    compare,
+                    baseline,
+                    jobs,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                year: args.opt_value_from_str("--year")?,
+                release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("extract") => AppArguments::Extract {
+                year: args.opt_value_from_str("--year")?,
+                list: args.contains("--list"),
+                block: args.opt_value_from_str("--block")?,
+                overwrite: args.contains("--overwrite"),
                 day: args.free_from_str()?,
+            },
+            Some("scaffold") => AppArguments::Scaffold {
+                year: args.opt_value_from_str("--year")?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                year: args.opt_value_from_str("--year")?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                examples: args.contains("--examples"),
+                watch: args.contains("--watch"),
+                visualize: VisualizeOptions {
+                    play: args.contains("--visualize"),
+                    export: args.opt_value_from_str("--export")?,
+                    fps: args.opt_value_from_str("--fps")?,
+                },
+                day: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,33 +170,67 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+            } => all::handle(year, release, jobs),
+            AppArguments::Time {
+                day,
+                year,
+                all,
+                store,
+                budget,
+                compare,
+                baseline,
+                jobs,
+            } => time::handle(day, year, all, store, budget, compare, baseline, jobs),
+            AppArguments::Verify {
+                day,
+                year,
+                release,
+                jobs,
+            } => verify::handle(day, year, release, jobs),
+            AppArguments::Download { day, year } => download::handle(day, year),
+            AppArguments::Read { day, year } => read::handle(day, year),
+            AppArguments::Extract {
+                day,
+                year,
+                list,
+                block,
+                overwrite,
+            } => extract::handle(day, year, list, block, overwrite),
             AppArguments::Scaffold {
                 day,
+                year,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, year, overwrite, template.as_deref());
                 if download {
-                    download::handle(day);
+                    download::handle(day, year);
                 }
             }
             AppArguments::Solve {
                 day,
+                year,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                examples,
+                watch,
+                visualize,
+            } => solve::handle(
+                day, year, release, dhat, submit, examples, watch, &visualize,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(day, None, false, None);
+                        download::handle(day, None);
+                        read::handle(day, None)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,438 @@
+/// Integer number theory: gcd and lcm, modular arithmetic, the Chinese Remainder Theorem and exact solving of
+/// small linear systems.
+///
+/// All functions are generic over the primitive integer types via [`Integer`], functions that need negative
+/// numbers require [`Signed`]. Functions named `checked_*` return [`None`] instead of overflowing. Modular
+/// multiplication never overflows, so [`mod_pow`] and [`crt`] work for moduli up to the maximum of `T`.
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// A primitive integer type.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    /// The absolute value, unsigned types return `self`.
+    fn abs(self) -> Self;
+}
+
+/// A primitive signed integer type.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_div(self, rhs)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            #[allow(unused_comparisons)]
+            fn abs(self) -> Self {
+                if self < 0 {
+                    Self::ZERO - self
+                } else {
+                    self
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/* -------------------------------------------------------------------------- */
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. `lcm(0, x)` is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Least common multiple, [`None`] if it overflows.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.abs() / gcd(a, b)).checked_mul(b.abs())
+}
+
+/// Least common multiple of all numbers, 1 for no numbers.
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    checked_lcm_all(numbers).expect("lcm overflowed")
+}
+
+/// Least common multiple of all numbers, [`None`] if it overflows.
+pub fn checked_lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers
+        .into_iter()
+        .try_fold(T::ONE, |acc, x| checked_lcm(acc, x))
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn egcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Sum of the integers in `from..to`, 0 if the range is empty.
+pub fn arithmetic_sum<T: Integer>(from: T, to: T) -> T {
+    checked_arithmetic_sum(from, to).expect("arithmetic sum overflowed")
+}
+
+/// Sum of the integers in `from..to`, [`None`] if it overflows.
+pub fn checked_arithmetic_sum<T: Integer>(from: T, to: T) -> Option<T> {
+    if to <= from {
+        return Some(T::ZERO);
+    }
+
+    let two = T::ONE + T::ONE;
+    let count = to.checked_sub(from)?;
+    let ends = from.checked_add(to)?.checked_sub(T::ONE)?;
+
+    // one of both factors is even, halve it first to avoid overflows.
+    if count % two == T::ZERO {
+        (count / two).checked_mul(ends)
+    } else {
+        count.checked_mul(ends / two)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// `a * b mod m` in `0..m`, without overflowing.
+///
+/// # Panics
+/// Panics if `m` is not positive.
+pub fn mod_mul<T: Integer>(a: T, b: T, m: T) -> T {
+    assert!(m > T::ZERO, "modulus must be positive");
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // double and add, every intermediate result stays below `m`.
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = mod_add(result, a, m);
+        }
+        a = mod_add(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// `a + b mod m` for `a` and `b` in `0..m`, without overflowing.
+fn mod_add<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `base^exp mod m` in `0..m`, by repeated squaring.
+///
+/// # Panics
+/// Panics if `m` is not positive or `exp` is negative.
+pub fn mod_pow<T: Integer>(base: T, exp: T, m: T) -> T {
+    assert!(m > T::ZERO, "modulus must be positive");
+    assert!(exp >= T::ZERO, "exponent must not be negative");
+    let two = T::ONE + T::ONE;
+
+    let mut result = T::ONE % m;
+    let mut base = base.rem_euclid(m);
+    let mut exp = exp;
+
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp = exp / two;
+    }
+    result
+}
+
+/// Modular inverse of `a` in `0..m`, [`None`] if `a` and `m` are not coprime.
+pub fn mod_inv<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// Chinese Remainder Theorem: solves `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs.
+/// The moduli must be positive but do not have to be coprime.
+///
+/// Returns `(x, m)` with the smallest non-negative solution `x` and the combined modulus `m`, i.e. all solutions
+/// are `x + k * m`. Returns [`None`] if the congruences contradict each other or `m` overflows.
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+
+    for &(residue, modulus) in congruences {
+        let residue = residue.rem_euclid(modulus);
+        let (g, p, _) = egcd(m, modulus);
+        let diff = residue - x;
+
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        // x + m * k solves both for k = diff / g * p mod (modulus / g), and m * k stays below the new modulus.
+        let k = mod_mul(diff / g, p, modulus / g);
+        let combined = (m / g).checked_mul(modulus)?;
+        x = x + m * k;
+        m = combined;
+    }
+
+    Some((x, m))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Solves `m * [x, y] = rhs` exactly with Cramer's rule.
+///
+/// Returns [`None`] if the system has no unique solution, the solution is not integral or a computation overflows.
+pub fn solve_2x2<T: Signed>(m: [[T; 2]; 2], rhs: [T; 2]) -> Option<[T; 2]> {
+    let det2 = |a: T, b: T, c: T, d: T| a.checked_mul(d)?.checked_sub(b.checked_mul(c)?);
+
+    let det = det2(m[0][0], m[0][1], m[1][0], m[1][1])?;
+    if det == T::ZERO {
+        return None;
+    }
+
+    let x = det2(rhs[0], m[0][1], rhs[1], m[1][1])?;
+    let y = det2(m[0][0], rhs[0], m[1][0], rhs[1])?;
+
+    if x % det != T::ZERO || y % det != T::ZERO {
+        return None;
+    }
+
+    Some([x / det, y / det])
+}
+
+/// Solves the square system `matrix * x = rhs` exactly, by fraction-free Gaussian elimination (Bareiss).
+///
+/// Returns [`None`] if the system has no unique solution, the solution is not integral or a computation overflows.
+///
+/// # Panics
+/// Panics if `matrix` is not square or `rhs` does not have a value per row.
+pub fn solve_linear<T: Signed>(matrix: &[Vec<T>], rhs: &[T]) -> Option<Vec<T>> {
+    let n = matrix.len();
+    assert!(
+        matrix.iter().all(|row| row.len() == n) && rhs.len() == n,
+        "expecting a square matrix with a value per row"
+    );
+
+    let mut a: Vec<Vec<T>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| row.iter().copied().chain([value]).collect())
+        .collect();
+
+    let mut previous_pivot = T::ONE;
+
+    for k in 0..n {
+        let pivot_row = (k..n).find(|&i| a[i][k] != T::ZERO)?;
+        a.swap(k, pivot_row);
+
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                // exact division, the numerator is always a multiple of the previous pivot.
+                a[i][j] = a[i][j]
+                    .checked_mul(a[k][k])?
+                    .checked_sub(a[i][k].checked_mul(a[k][j])?)?
+                    .checked_div(previous_pivot)?;
+            }
+            a[i][k] = T::ZERO;
+        }
+
+        previous_pivot = a[k][k];
+    }
+
+    let mut x = vec![T::ZERO; n];
+
+    for i in (0..n).rev() {
+        let mut value = a[i][n];
+        for j in i + 1..n {
+            value = value.checked_sub(a[i][j].checked_mul(x[j])?)?;
+        }
+
+        if value % a[i][i] != T::ZERO {
+            return None;
+        }
+        x[i] = value / a[i][i];
+    }
+
+    Some(x)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0u8, 5), 5);
+        assert_eq!(gcd(0, 0), 0);
+
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4i64, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm_all([u64::MAX, 2]), None);
+    }
+
+    #[test]
+    fn computes_egcd() {
+        for (a, b) in [(240, 46), (46, 240), (-7, 3), (0, 5), (5, 0)] {
+            let (g, x, y) = egcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_arithmetic_sums() {
+        assert_eq!(arithmetic_sum(3u64, 6), 12);
+        assert_eq!(arithmetic_sum(-2, 3), 0);
+        assert_eq!(arithmetic_sum(5, 5), 0);
+        assert_eq!(
+            arithmetic_sum(0u64, 2_000_000_000),
+            1_999_999_999_000_000_000
+        );
+        assert_eq!(checked_arithmetic_sum(0u8, 30), None);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2u64, 0, 1), 0);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(3u64, 1_000_000, 1_000_000_007), 64_935_414);
+
+        // the product of the residues overflows u64.
+        let m = u64::MAX - 58;
+        assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn rejects_non_positive_moduli() {
+        let _ = mod_pow(2, 0, -3);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(
+            crt(&[(0i64, 7), (12, 13), (55, 59), (25, 31), (12, 19)]).map(|x| x.0),
+            Some(1_068_781)
+        );
+        // moduli do not have to be coprime.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1i8, 100), (2, 99)]), None);
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some([80, 40])
+        );
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+
+        let matrix = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(solve_linear(&matrix, &[8, -11, -3]), Some(vec![2, 3, -1]));
+
+        let matrix = vec![vec![0, 1], vec![1, 0]];
+        assert_eq!(solve_linear(&matrix, &[5, 7]), Some(vec![7, 5]));
+
+        let matrix = vec![vec![2, 0], vec![0, 1]];
+        assert_eq!(solve_linear(&matrix, &[3, 1]), None);
+
+        let matrix = vec![vec![1, 1], vec![2, 2]];
+        assert_eq!(solve_linear(&matrix, &[1, 2]), None);
+
+        let matrix = vec![vec![i64::MAX, 1], vec![1, i64::MAX]];
+        assert_eq!(solve_linear(&matrix, &[1, 1]), None);
+    }
+}
@@ -0,0 +1,285 @@
+/// Caches for recursive solutions.
+///
+/// A [`Memo`] stores the results of a function by key. Recursive functions take the memo as an argument and wrap
+/// their body in [`Memo::get_or_compute`], which returns the cached result or computes and stores it:
+///
+/// ```
+/// # use advent_of_code::memo::Memo;
+/// fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fib(n - 1, memo) + fib(n - 2, memo),
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+/// println!("{}", memo.stats()); // 88 hits, 91 misses (49.2% hit rate), 91 entries
+/// ```
+///
+/// The cache is a [`HashMap`] by default. Switch to [`FxMemo`] for faster hashing or to [`DenseMemo`] for small
+/// integer keys, both have the same API.
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use crate::hash::FxHashMap;
+
+/// Storage of a [`Memo`].
+pub trait Cache<K, V>: Default {
+    fn lookup(&self, key: &K) -> Option<&V>;
+    fn store(&mut self, key: K, value: V);
+    /// Number of cached values.
+    fn entries(&self) -> usize;
+    fn clear(&mut self);
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> Cache<K, V> for HashMap<K, V, S> {
+    fn lookup(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+
+    fn entries(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self);
+    }
+}
+
+/// A dense cache for keys in `0..n`, which grows to the largest stored key.
+impl<V> Cache<usize, V> for Vec<Option<V>> {
+    fn lookup(&self, key: &usize) -> Option<&V> {
+        self.get(*key)?.as_ref()
+    }
+
+    fn store(&mut self, key: usize, value: V) {
+        if key >= self.len() {
+            self.resize_with(key + 1, || None);
+        }
+        self[key] = Some(value);
+    }
+
+    fn entries(&self) -> usize {
+        self.iter().filter(|value| value.is_some()).count()
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+}
+
+/// Cache statistics of a [`Memo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    /// Calls that returned a cached value.
+    pub hits: usize,
+    /// Calls that computed a value.
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl MemoStats {
+    /// Share of calls that returned a cached value, 0 if there were no calls.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            #[allow(clippy::cast_precision_loss)]
+            calls => self.hits as f64 / calls as f64,
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+/// Results of a function by key, see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Memo<K, V, C = HashMap<K, V>> {
+    cache: C,
+    hits: usize,
+    misses: usize,
+    marker: PhantomData<(K, V)>,
+}
+
+/// A [`Memo`] hashing keys with [`FxHasher`](crate::hash::FxHasher).
+pub type FxMemo<K, V> = Memo<K, V, FxHashMap<K, V>>;
+
+/// A [`Memo`] for keys in `0..n`, stored in a vector.
+pub type DenseMemo<V> = Memo<usize, V, Vec<Option<V>>>;
+
+impl<K, V, C: Cache<K, V>> Default for Memo<K, V, C> {
+    fn default() -> Self {
+        Memo {
+            cache: C::default(),
+            hits: 0,
+            misses: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V: Clone, C: Cache<K, V>> Memo<K, V, C> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value of `key` or computes it with `f`. `f` receives the memo for recursive calls.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.lookup(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = f(self);
+        self.cache.store(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value of `key` without updating the statistics.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.lookup(key)
+    }
+
+    /// Removes all cached values and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.entries(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone, S: BuildHasher + Default> Memo<K, V, HashMap<K, V, S>> {
+    /// Like [`Memo::get_or_compute`], but looks up a borrowed key, e.g. a `&str` for `String` keys.
+    /// The key is only converted to an owned key when a value is stored.
+    pub fn get_or_compute_borrowed<Q>(&mut self, key: &Q, f: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = f(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cache, DenseMemo, FxMemo, Memo, MemoStats};
+
+    fn fib<C: Cache<u64, u64>>(n: u64, memo: &mut Memo<u64, u64, C>) -> u64 {
+        memo.get_or_compute(n, |memo| match n {
+            0 | 1 => n,
+            _ => fib(n - 1, memo) + fib(n - 2, memo),
+        })
+    }
+
+    #[test]
+    fn memoizes_recursive_functions() {
+        let mut memo: Memo<u64, u64> = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 88,
+                misses: 91,
+                entries: 91
+            }
+        );
+        assert_eq!(memo.get(&10), Some(&55));
+
+        let mut memo: FxMemo<u64, u64> = FxMemo::new();
+        assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.stats().entries, 91);
+
+        memo.clear();
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 0,
+                misses: 0,
+                entries: 0
+            }
+        );
+    }
+
+    #[test]
+    fn uses_dense_caches() {
+        fn count_paths(n: usize, memo: &mut DenseMemo<u64>) -> u64 {
+            memo.get_or_compute(n, |memo| match n {
+                0 => 1,
+                1 => 1,
+                _ => count_paths(n - 1, memo) + count_paths(n - 2, memo),
+            })
+        }
+
+        let mut memo = DenseMemo::new();
+        assert_eq!(count_paths(50, &mut memo), 20_365_011_074);
+        assert_eq!(memo.stats().entries, 51);
+        assert_eq!(memo.get(&100), None);
+    }
+
+    #[test]
+    fn looks_up_borrowed_keys() {
+        fn suffixes(s: &str, memo: &mut Memo<String, usize>) -> usize {
+            memo.get_or_compute_borrowed(s, |memo| match s.len() {
+                0 => 1,
+                _ => 1 + suffixes(&s[1..], memo),
+            })
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(suffixes("abc", &mut memo), 4);
+        assert_eq!(suffixes("xbc", &mut memo), 4);
+        assert_eq!(memo.stats().hits, 1);
+        assert_eq!(memo.get(&"bc".to_string()), Some(&3));
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = MemoStats {
+            hits: 1,
+            misses: 3,
+            entries: 3,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "1 hits, 3 misses (25.0% hit rate), 3 entries"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+/// Recognizes the block letters some puzzles use for their answers.
+///
+/// Puzzles draw capital letters with `#` in two fonts: 4x6 letters (e.g. 2016 day 8, 2022 day 10)
+/// and 6x10 letters (e.g. 2018 day 10). The font is selected by the height of the lit area,
+/// letters are separated by blank columns.
+///
+/// ```
+/// # use advent_of_code::ocr;
+/// let rendering = [
+///     "#..#.###.",
+///     "#..#..#..",
+///     "####..#..",
+///     "#..#..#..",
+///     "#..#..#..",
+///     "#..#.###.",
+/// ];
+/// assert_eq!(ocr::recognize_str(&rendering.join("\n")), Ok("HI".into()));
+/// ```
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::grid::Grid;
+
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Recognizes the letters drawn by the `true` cells of a grid.
+pub fn recognize(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = grid.rows().map(<[bool]>::to_vec).collect();
+    recognize_rows(&rows)
+}
+
+/// Recognizes the letters of a string rendering, where `#` and `█` are lit and all other characters are not.
+pub fn recognize_str(s: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = s
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    recognize_rows(&rows)
+}
+
+/// An error which can be returned when recognizing letters.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// No cell is lit.
+    Empty,
+    /// The lit area is neither 6 nor 10 rows high.
+    UnsupportedHeight(usize),
+    /// A glyph does not match any letter of the font, contains the glyph rendered with `#` and `.`.
+    UnknownGlyph(String),
+}
+
+impl Error for OcrError {}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => f.write_str("expecting at least one lit cell"),
+            OcrError::UnsupportedHeight(height) => {
+                write!(
+                    f,
+                    "expecting letters to be 6 or 10 rows high, found {height}"
+                )
+            }
+            OcrError::UnknownGlyph(glyph) => write!(f, "unknown glyph:\n{glyph}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn recognize_rows(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let is_lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    // crop to the rows that contain lit cells.
+    let lit_rows: Vec<usize> = (0..rows.len())
+        .filter(|&y| rows[y].iter().any(|&c| c))
+        .collect();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Err(OcrError::Empty);
+    };
+
+    let font: &[(char, &str)] = match bottom - top + 1 {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let is_blank_column = |x: usize| (top..=bottom).all(|y| !is_lit(x, y));
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let glyph = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let letter = font
+            .iter()
+            .find(|(_, pattern)| trim_glyph(pattern) == glyph)
+            .map(|(letter, _)| *letter)
+            .ok_or(OcrError::UnknownGlyph(glyph))?;
+
+        text.push(letter);
+    }
+
+    Ok(text)
+}
+
+/// Removes blank leading and trailing columns from a font glyph.
+fn trim_glyph(pattern: &str) -> String {
+    let lines: Vec<&[u8]> = pattern.lines().map(str::as_bytes).collect();
+    let width = lines[0].len();
+    let is_blank = |x: usize| lines.iter().all(|line| line[x] != b'#');
+
+    let start = (0..width).find(|&x| !is_blank(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| !is_blank(x)).map_or(width, |x| x + 1);
+
+    lines
+        .iter()
+        .map(|line| String::from_utf8_lossy(&line[start..end]).into_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, recognize_str, OcrError, FONT_10, FONT_6};
+    use crate::grid::Grid;
+
+    /// Renders a word in a font, with `gap` blank columns between letters.
+    fn render(font: &[(char, &str)], word: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = word
+            .chars()
+            .map(|c| {
+                font.iter()
+                    .find(|(l, _)| *l == c)
+                    .unwrap()
+                    .1
+                    .lines()
+                    .collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognizes_every_letter() {
+        for (letter, pattern) in FONT_6.iter().chain(FONT_10.iter()) {
+            assert_eq!(recognize_str(pattern), Ok(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn recognizes_small_letters() {
+        let rendering = render(&FONT_6, "EHZGPJFO", 1);
+        assert_eq!(recognize_str(&rendering), Ok("EHZGPJFO".into()));
+    }
+
+    #[test]
+    fn recognizes_large_letters() {
+        let rendering = render(&FONT_10, "HJNRXZ", 2);
+        assert_eq!(recognize_str(&rendering), Ok("HJNRXZ".into()));
+    }
+
+    #[test]
+    fn ignores_padding() {
+        let letters = render(&FONT_6, "AB", 1);
+        let padded: Vec<String> = letters.lines().map(|line| format!("..{line}..")).collect();
+        let rendering = format!("\n..........\n{}\n  ", padded.join("\n"));
+        assert_eq!(recognize_str(&rendering), Ok("AB".into()));
+    }
+
+    #[test]
+    fn recognizes_grids() {
+        let grid = Grid::parse_with(&render(&FONT_6, "CK", 1), |c| c == '#').unwrap();
+        assert_eq!(recognize(&grid), Ok("CK".into()));
+    }
+
+    #[test]
+    fn errors_for_unknown_input() {
+        assert_eq!(recognize_str("...\n..."), Err(OcrError::Empty));
+        assert_eq!(
+            recognize_str("#\n#\n#"),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+        assert_eq!(
+            recognize_str("######\n#\n#\n#\n#\n#"),
+            Err(OcrError::UnknownGlyph(
+                "######\n#.....\n#.....\n#.....\n#.....\n#.....".into()
+            ))
+        );
+    }
+}
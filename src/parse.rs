@@ -0,0 +1,430 @@
+/// Helpers for parsing puzzle inputs without panicking.
+///
+/// Errors are [`ParseError`]s that point at the line and column of the offending text, e.g.
+/// `line 3, column 7: invalid digit found in string ("1x")`.
+///
+/// ```
+/// # use advent_of_code::parse::{blocks, ints, Pattern};
+/// let robots: Vec<(i32, i32, i32, i32)> = Pattern::new("p={},{} v={},{}")
+///     .parse_lines("p=0,4 v=3,-3\np=6,3 v=-1,-3")
+///     .unwrap();
+/// assert_eq!(robots[1], (6, 3, -1, -3));
+///
+/// assert_eq!(ints::<i64>("x=-3..12").unwrap(), vec![-3, 12]);
+/// assert_eq!(blocks("a\nb\n\nc\n").collect::<Vec<_>>(), vec!["a\nb", "c"]);
+/// ```
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error which can be returned when parsing an input. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error at the start of `part`, which must be a slice of `text`.
+    pub fn at(text: &str, part: &str, message: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize).saturating_sub(text.as_ptr() as usize);
+        let before = &text[..offset.min(text.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Parses `part`, a slice of `text`, reporting errors at its position in `text`.
+fn parse_value<T: FromStr>(text: &str, part: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    part.parse()
+        .map_err(|e| ParseError::at(text, part, format!("{e} ({part:?})")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all integers from a text, ignoring everything between them.
+///
+/// A `-` directly before a number is its sign unless it follows a letter or digit, so ranges like `3-5` are
+/// read as `3` and `5`. Fails if a number does not fit into `T`.
+pub fn ints<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    extract_numbers(s, true)
+}
+
+/// Extracts all unsigned integers from a text, ignoring everything between them including `-`.
+pub fn uints<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    extract_numbers(s, false)
+}
+
+fn extract_numbers<T: FromStr>(s: &str, signed: bool) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    let bytes = s.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let has_sign = signed
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+        if has_sign {
+            start -= 1;
+        }
+
+        numbers.push(parse_value(s, &s[start..i])?);
+    }
+
+    Ok(numbers)
+}
+
+/// Splits a text into blocks separated by blank lines, e.g. the sections of an input.
+/// Trailing line breaks of blocks and blocks without content are dropped.
+pub fn blocks(s: &str) -> impl Iterator<Item = &str> {
+    let mut blocks = vec![];
+    let mut start = 0;
+    let mut offset = 0;
+
+    for line in s.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            blocks.push(&s[start..offset]);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    blocks.push(&s[start..]);
+
+    blocks
+        .into_iter()
+        .map(|block| block.trim_end_matches(['\r', '\n']))
+        .filter(|block| !block.trim().is_empty())
+}
+
+/// Parses every line of a text with [`FromStr`]. Empty lines are skipped.
+pub fn lines<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_value(s, line.trim_end()))
+        .collect()
+}
+
+/// Parses every line of a text with `f`. Empty lines are skipped, errors of `f` point at the start of the line.
+pub fn lines_with<'a, T, E: Display>(
+    s: &'a str,
+    f: impl Fn(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| f(line).map_err(|e| ParseError::at(s, line, e.to_string())))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A pattern of literal text and `{}` placeholders, e.g. `"p={},{} v={},{}"`.
+///
+/// Every placeholder captures the text up to the next occurrence of the literal following it, the last placeholder
+/// captures the rest of the line if the pattern ends with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern<'p> {
+    /// The literals around the placeholders, one more than there are placeholders.
+    literals: Vec<&'p str>,
+}
+
+impl<'p> Pattern<'p> {
+    /// # Panics
+    /// Panics if two placeholders are adjacent, as it is ambiguous where the first capture ends.
+    pub fn new(pattern: &'p str) -> Self {
+        let literals: Vec<&str> = pattern.split("{}").collect();
+        assert!(
+            literals.len() < 3
+                || literals[1..literals.len() - 1]
+                    .iter()
+                    .all(|l| !l.is_empty()),
+            "placeholders of pattern \"{pattern}\" must be separated by text"
+        );
+        Pattern { literals }
+    }
+
+    /// Returns the captured text of every placeholder.
+    pub fn captures<'s>(&self, s: &'s str) -> Result<Vec<&'s str>, ParseError> {
+        self.captures_in(s, s)
+    }
+
+    /// Like [`Pattern::captures`], but `s` is a slice of `text` and errors point into `text`.
+    fn captures_in<'s>(&self, text: &str, s: &'s str) -> Result<Vec<&'s str>, ParseError> {
+        let prefix = self.literals[0];
+        if !s.starts_with(prefix) {
+            return Err(ParseError::at(text, s, format!("expected {prefix:?}")));
+        }
+
+        let mut captures = vec![];
+        let mut pos = prefix.len();
+
+        for literal in &self.literals[1..] {
+            let end = if literal.is_empty() {
+                s.len()
+            } else {
+                match s[pos..].find(literal) {
+                    Some(i) => pos + i,
+                    None => {
+                        return Err(ParseError::at(
+                            text,
+                            &s[s.len()..],
+                            format!("expected {literal:?}"),
+                        ))
+                    }
+                }
+            };
+
+            if end == pos {
+                return Err(ParseError::at(text, &s[pos..], "expected a value"));
+            }
+
+            captures.push(&s[pos..end]);
+            pos = end + literal.len();
+        }
+
+        if pos < s.len() {
+            return Err(ParseError::at(
+                text,
+                &s[pos..],
+                format!("unexpected {:?}", &s[pos..]),
+            ));
+        }
+
+        Ok(captures)
+    }
+
+    /// Parses the captures into a tuple or vector of [`FromStr`] values.
+    pub fn parse<T: FromCaptures>(&self, s: &str) -> Result<T, ParseError> {
+        T::from_captures(s, &self.captures(s)?)
+    }
+
+    /// Parses every line of a text like [`Pattern::parse`]. Empty lines are skipped.
+    pub fn parse_lines<T: FromCaptures>(&self, s: &str) -> Result<Vec<T>, ParseError> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| T::from_captures(s, &self.captures_in(s, line.trim_end())?))
+            .collect()
+    }
+}
+
+/// Types that can be built from the captures of a [`Pattern`].
+pub trait FromCaptures: Sized {
+    /// Builds a value from `captures`, which are slices of `text`.
+    fn from_captures(text: &str, captures: &[&str]) -> Result<Self, ParseError>;
+}
+
+impl<T: FromStr> FromCaptures for Vec<T>
+where
+    T::Err: Display,
+{
+    fn from_captures(text: &str, captures: &[&str]) -> Result<Self, ParseError> {
+        captures
+            .iter()
+            .map(|capture| parse_value(text, capture))
+            .collect()
+    }
+}
+
+macro_rules! impl_from_captures {
+    ($len:literal: $($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> FromCaptures for ($($t,)+)
+        where
+            $($t::Err: Display),+
+        {
+            fn from_captures(text: &str, captures: &[&str]) -> Result<Self, ParseError> {
+                if captures.len() != $len {
+                    return Err(ParseError::at(
+                        text,
+                        captures.first().copied().unwrap_or(text),
+                        format!("expected {} values, found {}", $len, captures.len()),
+                    ));
+                }
+                Ok(($(parse_value::<$t>(text, captures[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1: A 0);
+impl_from_captures!(2: A 0, B 1);
+impl_from_captures!(3: A 0, B 1, C 2);
+impl_from_captures!(4: A 0, B 1, C 2, D 3);
+impl_from_captures!(5: A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, ints, lines, lines_with, uints, ParseError, Pattern};
+
+    fn error(line: usize, column: usize, message: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn extracts_ints() {
+        assert_eq!(ints::<i32>("p=0,-4 v=-3,3").unwrap(), vec![0, -4, -3, 3]);
+        assert_eq!(ints::<i32>("1-3 a-2 -5").unwrap(), vec![1, 3, 2, -5]);
+        assert_eq!(ints::<i32>("no numbers").unwrap(), Vec::<i32>::new());
+        assert_eq!(uints::<u64>("x=-3, y=12\n7").unwrap(), vec![3, 12, 7]);
+
+        assert_eq!(
+            ints::<u8>("1\nx=300").unwrap_err(),
+            error(2, 3, "number too large to fit in target type (\"300\")")
+        );
+        assert_eq!(
+            ints::<u32>("-1").unwrap_err(),
+            error(1, 1, "invalid digit found in string (\"-1\")")
+        );
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let blocks: Vec<_> = blocks("a\nb\n\n\nc\r\n\r\nd\n  \ne\n\n").collect();
+        assert_eq!(blocks, vec!["a\nb", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(lines::<u32>("1\n2\n\n3\n").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            lines::<u32>("1\n2x\n").unwrap_err().to_string(),
+            "line 2, column 1: invalid digit found in string (\"2x\")"
+        );
+
+        let parsed = lines_with("a 1\nb 2", |line| {
+            let (name, value) = line.split_once(' ').ok_or("missing space")?;
+            Ok::<_, String>((name.to_string(), value.to_string()))
+        });
+        assert_eq!(parsed.unwrap()[1], ("b".into(), "2".into()));
+        assert_eq!(
+            lines_with("a 1\nb", |line| line.split_once(' ').ok_or("missing space")).unwrap_err(),
+            error(2, 1, "missing space")
+        );
+    }
+
+    #[test]
+    fn matches_patterns() {
+        let pattern = Pattern::new("p={},{} v={},{}");
+        assert_eq!(
+            pattern.captures("p=0,4 v=3,-3").unwrap(),
+            vec!["0", "4", "3", "-3"]
+        );
+        assert_eq!(
+            pattern
+                .parse::<(i32, i32, i32, i32)>("p=0,4 v=3,-3")
+                .unwrap(),
+            (0, 4, 3, -3)
+        );
+        assert_eq!(
+            pattern.parse::<Vec<i32>>("p=0,4 v=3,-3").unwrap(),
+            vec![0, 4, 3, -3]
+        );
+
+        let pattern = Pattern::new("{} -> {}");
+        assert_eq!(
+            pattern.parse::<(String, String)>("a -> b -> c").unwrap(),
+            ("a".into(), "b -> c".into())
+        );
+
+        let pattern = Pattern::new("Register {}: {}");
+        assert_eq!(
+            pattern
+                .parse_lines::<(char, u64)>("Register A: 729\nRegister B: 0\n")
+                .unwrap(),
+            vec![('A', 729), ('B', 0)]
+        );
+    }
+
+    #[test]
+    fn reports_pattern_errors() {
+        let pattern = Pattern::new("p={},{} v={},{}");
+
+        assert_eq!(
+            pattern.captures("q=0,4 v=3,-3").unwrap_err(),
+            error(1, 1, "expected \"p=\"")
+        );
+        assert_eq!(
+            pattern.captures("p=0,4").unwrap_err(),
+            error(1, 6, "expected \" v=\"")
+        );
+        assert_eq!(
+            pattern.captures("p=,4 v=3,-3").unwrap_err(),
+            error(1, 3, "expected a value")
+        );
+
+        let text = "1 2\n3";
+        assert_eq!(
+            ParseError::at(text, &text[text.len()..], "expected 4 numbers"),
+            error(2, 2, "expected 4 numbers")
+        );
+        assert_eq!(
+            pattern
+                .parse_lines::<(i32, i32, i32, i32)>("p=0,4 v=3,-3\np=6,3 v=x,-3")
+                .unwrap_err(),
+            error(2, 9, "invalid digit found in string (\"x\")")
+        );
+        assert_eq!(
+            Pattern::new("{},").parse::<(u32,)>("1,2,").unwrap_err(),
+            error(1, 3, "unexpected \"2,\"")
+        );
+        assert_eq!(
+            pattern.parse::<(i32, i32)>("p=0,4 v=3,-3").unwrap_err(),
+            error(1, 3, "expected 2 values, found 4")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be separated by text")]
+    fn rejects_adjacent_placeholders() {
+        let _ = Pattern::new("{}{}");
+    }
+}
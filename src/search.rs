@@ -0,0 +1,501 @@
+/// Generic graph searches over a successor closure.
+///
+/// Nodes can be any hashable value (e.g. a [`Point`](crate::grid::Point) or a `(Point, Direction)` state),
+/// the graph itself is never materialized: every search calls `successors` with a node and expects the reachable
+/// neighbours back, weighted searches expect `(neighbour, cost)` pairs.
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Edge weights usable by [`dijkstra`] and [`astar`]. `C::default()` is used as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// Shortest distances from a start node to every reached node, with one parent per node to reconstruct paths.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    /// Returns the cost of the shortest path to `node`, [`None`] if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// Returns the shortest costs of all reached nodes, including the start.
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// Iterates over all reached nodes, including the start.
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.costs.keys()
+    }
+
+    /// Returns the node preceding `node` on its shortest path.
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.parents.get(node)
+    }
+
+    /// Reconstructs the shortest path from the start to `goal`, both included.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.contains(goal) {
+            return None;
+        }
+
+        let mut path = vec![goal.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Shortest distances from a start node along with *all* predecessors lying on a shortest path.
+#[derive(Debug, Clone)]
+pub struct AllPaths<N, C> {
+    costs: HashMap<N, C>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> AllPaths<N, C> {
+    /// Returns the cost of the shortest path to `node`, [`None`] if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// Returns the shortest costs of all reached nodes, including the start.
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// Returns every node that precedes `node` on some shortest path.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Collects every node lying on any shortest path from the start to one of `goals`.
+    pub fn nodes_on_paths_to(&self, goals: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<N> = goals
+            .into_iter()
+            .filter(|goal| self.costs.contains_key(goal))
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+
+        seen
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from `start`, visiting every reachable node.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths {
+        costs: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+    };
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, cost)) = queue.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = paths.costs.entry(next.clone()) {
+                entry.insert(cost + 1);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Breadth-first search from `start` that stops at the first node satisfying `is_goal`.
+/// Returns the path to it (both ends included) and its length in steps.
+pub fn bfs_to<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths {
+        costs: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+    };
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if is_goal(&node) {
+            return paths.path_to(&node).map(|path| (path, cost));
+        }
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = paths.costs.entry(next.clone()) {
+                entry.insert(cost + 1);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns every node reachable from `start`, including `start` itself. Useful for flood fills.
+pub fn reachable<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Dijkstra's algorithm from `start`, visiting every reachable node.
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        costs: HashMap::from([(start.clone(), C::default())]),
+        parents: HashMap::new(),
+    };
+    let mut queue = BinaryHeap::from([State::new(start, C::default())]);
+
+    while let Some(State { node, cost, .. }) = queue.pop() {
+        if paths.costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if relax(&mut paths.costs, &next, next_cost) == Relaxed::Improved {
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push(State::new(next, next_cost));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm from `start` that stops at the first settled node satisfying `is_goal`.
+/// Returns the path to it (both ends included) and its cost.
+pub fn dijkstra_to<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search from `start` to the first settled node satisfying `is_goal`.
+///
+/// `heuristic` must never overestimate the remaining cost to a goal, otherwise the returned path is not guaranteed
+/// to be optimal. Returns the path (both ends included) and its cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        costs: HashMap::from([(start.clone(), C::default())]),
+        parents: HashMap::new(),
+    };
+    let estimate = heuristic(&start);
+    let mut queue = BinaryHeap::from([State::with_estimate(start, C::default(), estimate)]);
+
+    while let Some(State { node, cost, .. }) = queue.pop() {
+        if paths.costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return paths.path_to(&node).map(|path| (path, cost));
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if relax(&mut paths.costs, &next, next_cost) == Relaxed::Improved {
+                paths.parents.insert(next.clone(), node.clone());
+                let estimate = next_cost + heuristic(&next);
+                queue.push(State::with_estimate(next, next_cost, estimate));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm from `start` that records every predecessor on a shortest path, not just one.
+///
+/// Use [`AllPaths::nodes_on_paths_to`] to answer "which nodes lie on any best path" questions.
+pub fn dijkstra_all<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> AllPaths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = AllPaths {
+        costs: HashMap::from([(start.clone(), C::default())]),
+        predecessors: HashMap::new(),
+    };
+    let mut queue = BinaryHeap::from([State::new(start, C::default())]);
+
+    while let Some(State { node, cost, .. }) = queue.pop() {
+        if paths.costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            match relax(&mut paths.costs, &next, next_cost) {
+                Relaxed::Improved => {
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push(State::new(next, next_cost));
+                }
+                Relaxed::Tied => {
+                    paths
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                Relaxed::Worse => {}
+            }
+        }
+    }
+
+    paths
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(PartialEq, Eq)]
+enum Relaxed {
+    Improved,
+    Tied,
+    Worse,
+}
+
+/// Records `cost` for `node` if it is better than the best known one.
+fn relax<N: Eq + Hash + Clone, C: Cost>(costs: &mut HashMap<N, C>, node: &N, cost: C) -> Relaxed {
+    match costs.get_mut(node) {
+        Some(best) => match cost.cmp(best) {
+            Ordering::Less => {
+                *best = cost;
+                Relaxed::Improved
+            }
+            Ordering::Equal => Relaxed::Tied,
+            Ordering::Greater => Relaxed::Worse,
+        },
+        None => {
+            costs.insert(node.clone(), cost);
+            Relaxed::Improved
+        }
+    }
+}
+
+/// Priority queue entry. The ordering is reversed so that [`BinaryHeap`] pops the lowest estimate first.
+struct State<N, C> {
+    node: N,
+    cost: C,
+    estimate: C,
+}
+
+impl<N, C: Copy> State<N, C> {
+    fn new(node: N, cost: C) -> Self {
+        Self::with_estimate(node, cost, cost)
+    }
+
+    fn with_estimate(node: N, cost: C, estimate: C) -> Self {
+        Self {
+            node,
+            cost,
+            estimate,
+        }
+    }
+}
+
+impl<N, C: Ord> Ord for State<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for State<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for State<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for State<N, C> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::grid::{Grid, Point};
+
+    fn get_mock_maze() -> Grid<char> {
+        ["S..#", ".#..", "...E"].join("\n").parse().unwrap()
+    }
+
+    fn open_neighbors(grid: &Grid<char>, point: Point) -> Vec<Point> {
+        grid.neighbors4(point)
+            .filter(|next| grid[*next] != '#')
+            .collect()
+    }
+
+    mod unweighted {
+        use super::{get_mock_maze, open_neighbors};
+        use crate::grid::Point;
+        use crate::search::{bfs, bfs_to, reachable};
+
+        #[test]
+        fn finds_all_distances() {
+            let grid = get_mock_maze();
+            let paths = bfs(Point::new(0, 0), |p| open_neighbors(&grid, *p));
+            assert_eq!(paths.cost(&Point::new(3, 2)), Some(5));
+            assert_eq!(paths.cost(&Point::new(3, 0)), None);
+            assert_eq!(paths.reached().count(), 10);
+            assert_eq!(paths.path_to(&Point::new(3, 2)).unwrap().len(), 6);
+        }
+
+        #[test]
+        fn stops_at_goal() {
+            let grid = get_mock_maze();
+            let (path, len) = bfs_to(
+                Point::new(0, 0),
+                |p| open_neighbors(&grid, *p),
+                |p| grid[*p] == 'E',
+            )
+            .unwrap();
+            assert_eq!(len, 5);
+            assert_eq!(path.first(), Some(&Point::new(0, 0)));
+            assert_eq!(path.last(), Some(&Point::new(3, 2)));
+        }
+
+        #[test]
+        fn returns_none_for_unreachable_goal() {
+            let grid = get_mock_maze();
+            let res = bfs_to(
+                Point::new(0, 0),
+                |p| open_neighbors(&grid, *p),
+                |p| *p == Point::new(3, 0),
+            );
+            assert!(res.is_none());
+        }
+
+        #[test]
+        fn flood_fills() {
+            let grid = get_mock_maze();
+            let region = reachable(Point::new(0, 0), |p| open_neighbors(&grid, *p));
+            assert_eq!(region.len(), 10);
+        }
+    }
+
+    mod weighted {
+        use crate::search::{astar, dijkstra, dijkstra_all, dijkstra_to};
+
+        /// 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 4, the direct edge 0 -> 3 costs 5.
+        fn successors(node: &u8) -> Vec<(u8, u32)> {
+            match node {
+                0 => vec![(1, 1), (2, 2), (3, 5)],
+                1 => vec![(3, 3)],
+                2 => vec![(3, 2)],
+                3 => vec![(4, 1)],
+                _ => vec![],
+            }
+        }
+
+        #[test]
+        fn finds_all_costs() {
+            let paths = dijkstra(0, successors);
+            assert_eq!(paths.cost(&3), Some(4));
+            assert_eq!(paths.cost(&4), Some(5));
+            assert_eq!(paths.path_to(&4).unwrap().len(), 4);
+        }
+
+        #[test]
+        fn stops_at_goal() {
+            let (path, cost) = dijkstra_to(0, successors, |n| *n == 4).unwrap();
+            assert_eq!(cost, 5);
+            assert_eq!(path.first(), Some(&0));
+            assert_eq!(path.last(), Some(&4));
+        }
+
+        #[test]
+        fn uses_heuristic() {
+            let (_, cost) =
+                astar(0, successors, |n| u32::from(4 - n.min(&4)), |n| *n == 4).unwrap();
+            assert_eq!(cost, 5);
+        }
+
+        #[test]
+        fn collects_nodes_on_all_best_paths() {
+            let paths = dijkstra_all(0, successors);
+            let mut preds = paths.predecessors(&3).to_vec();
+            preds.sort_unstable();
+            assert_eq!(preds, [1, 2]);
+
+            let mut nodes: Vec<u8> = paths.nodes_on_paths_to([4]).into_iter().collect();
+            nodes.sort_unstable();
+            assert_eq!(nodes, [0, 1, 2, 3, 4]);
+        }
+    }
+}
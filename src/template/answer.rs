@@ -0,0 +1,202 @@
+use std::fmt::Display;
+
+/// The answer to a part of a puzzle.
+///
+/// Parts can return any [`Display`] value, answers are used to tell numbers, text and multiline "ASCII art" apart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Answers spanning several lines, e.g. letters drawn as ASCII art.
+    Multiline(String),
+}
+
+impl Answer {
+    /// Converts a rendered answer, detecting integers and multiline answers.
+    ///
+    /// Only canonical integers are detected, so text such as `"007"` or `"+5"` is kept verbatim.
+    pub fn parse(s: &str) -> Self {
+        if s.contains('\n') {
+            Answer::Multiline(s.into())
+        } else if let Some(n) = s.parse::<i128>().ok().filter(|n| n.to_string() == s) {
+            Answer::Integer(n)
+        } else {
+            Answer::Text(s.into())
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Multiline(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Multiline(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(i128::from(n))
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if s.contains('\n') {
+            Answer::Multiline(s)
+        } else {
+            Answer::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+/// The outcome of running a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    /// The part returned [`None`].
+    NotImplemented,
+    /// The part returned an error.
+    Failed(String),
+}
+
+impl Outcome {
+    pub fn answer(&self) -> Option<&Answer> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Outcome::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Return types of parts: `Option<T>`, `Result<T, E>`, [`Answer`] and [`Outcome`], where `T` and `E` are [`Display`].
+pub trait IntoOutcome {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display> IntoOutcome for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(Answer::parse(&answer.to_string())),
+            None => Outcome::NotImplemented,
+        }
+    }
+}
+
+impl<T: Display, E: Display> IntoOutcome for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(Answer::parse(&answer.to_string())),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+impl IntoOutcome for Answer {
+    fn into_outcome(self) -> Outcome {
+        Outcome::Solved(self)
+    }
+}
+
+impl IntoOutcome for Outcome {
+    fn into_outcome(self) -> Outcome {
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, IntoOutcome, Outcome};
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(Answer::from(42_u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-3_isize), Answer::Integer(-3));
+        assert_eq!(Answer::from("6,1"), Answer::Text("6,1".into()));
+        assert_eq!(Answer::from("#.\n.#"), Answer::Multiline("#.\n.#".into()));
+        assert_eq!(Answer::parse("123"), Answer::Integer(123));
+        assert_eq!(Answer::parse("-42"), Answer::Integer(-42));
+        assert_eq!(Answer::parse("007"), Answer::Text("007".into()));
+        assert_eq!(Answer::parse("+5"), Answer::Text("+5".into()));
+        assert_eq!(Answer::parse("-0"), Answer::Text("-0".into()));
+        assert_eq!(
+            Answer::Integer(u64::MAX.into()).to_string(),
+            u64::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn converts_return_types() {
+        assert_eq!(
+            Some(161).into_outcome(),
+            Outcome::Solved(Answer::Integer(161))
+        );
+        assert_eq!(None::<u32>.into_outcome(), Outcome::NotImplemented);
+
+        let ok: Result<&str, String> = Ok("abc");
+        assert_eq!(
+            ok.into_outcome(),
+            Outcome::Solved(Answer::Text("abc".into()))
+        );
+
+        let padded: Result<String, String> = Ok("007".into());
+        assert_eq!(
+            padded.into_outcome(),
+            Outcome::Solved(Answer::Text("007".into()))
+        );
+
+        let err: Result<u32, String> = Err("no path found".into());
+        assert_eq!(err.into_outcome(), Outcome::Failed("no path found".into()));
+        assert_eq!(err_outcome().error(), Some("no path found"));
+
+        assert!(Answer::from("#\n#")
+            .into_outcome()
+            .answer()
+            .unwrap()
+            .is_multiline());
+    }
+
+    fn err_outcome() -> Outcome {
+        Outcome::Failed("no path found".into())
+    }
+}
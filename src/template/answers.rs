@@ -0,0 +1,441 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Hint, SubmissionOutcome};
+use crate::template::{data_dir, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// The recorded verdict for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Converts a submission outcome into a verdict worth recording.
+    /// Rate limits and other responses do not tell us anything about the answer.
+    pub fn from_outcome(outcome: &SubmissionOutcome) -> Option<Self> {
+        match outcome {
+            SubmissionOutcome::Correct => Some(Verdict::Correct),
+            SubmissionOutcome::Incorrect(None) => Some(Verdict::Incorrect),
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh)) => Some(Verdict::TooHigh),
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow)) => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single answer that was submitted for a part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// What the ledger knows about an answer before it is submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// Nothing is known about this answer, it is safe to submit.
+    Unknown,
+    /// The part was already solved with the contained answer.
+    Solved(String),
+    /// The exact answer was submitted before and was wrong.
+    KnownWrong(Verdict),
+    /// The answer lies beyond a previously submitted answer that was too high or too low.
+    ContradictsBound { bound: String, verdict: Verdict },
+}
+
+/// Represents all answers submitted so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+impl Answers {
+    /// Dehydrate answers of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers of a year from a JSON file. If not present, returns an empty ledger.
+    ///
+    /// Unreadable or malformed files are an error rather than an empty ledger, so they are never overwritten.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        let path = data_dir(year).join(ANSWERS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                Answers::try_from(contents).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Records the outcome of a submission. Returns `false` if the outcome carried no verdict.
+    pub fn record(
+        &mut self,
+        day: Day,
+        part: u8,
+        answer: &str,
+        outcome: &SubmissionOutcome,
+    ) -> bool {
+        let Some(verdict) = Verdict::from_outcome(outcome) else {
+            return false;
+        };
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+        });
+
+        true
+    }
+
+    /// Returns the accepted answer for a part, if any.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.submissions(day, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.correct_answer(day, part).is_some()
+    }
+
+    /// Checks an answer against everything recorded for the part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> AnswerCheck {
+        if let Some(correct) = self.correct_answer(day, part) {
+            return AnswerCheck::Solved(correct.into());
+        }
+
+        if let Some(known) = self.submissions(day, part).find(|s| s.answer == answer) {
+            return AnswerCheck::KnownWrong(known.verdict);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return AnswerCheck::Unknown;
+        };
+
+        self.submissions(day, part)
+            .find(|s| match (s.verdict, s.answer.parse::<i128>()) {
+                (Verdict::TooHigh, Ok(bound)) => value >= bound,
+                (Verdict::TooLow, Ok(bound)) => value <= bound,
+                _ => false,
+            })
+            .map_or(AnswerCheck::Unknown, |s| AnswerCheck::ContradictsBound {
+                bound: s.answer.clone(),
+                verdict: s.verdict,
+            })
+    }
+
+    fn submissions(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| *part == 1 || *part == 2)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answers, Submission, Verdict};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "100".into(),
+                    verdict: Verdict::TooHigh,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "10".into(),
+                    verdict: Verdict::TooLow,
+                },
+                Submission {
+                    day: day!(1),
+                    part: 1,
+                    answer: "50".into(),
+                    verdict: Verdict::Incorrect,
+                },
+                Submission {
+                    day: day!(2),
+                    part: 1,
+                    answer: "abc".into(),
+                    verdict: Verdict::Correct,
+                },
+            ],
+        }
+    }
+
+    mod deserialization {
+        use crate::{
+            day,
+            template::answers::{Answers, Verdict},
+        };
+
+        #[test]
+        fn handles_json_answers() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part": 2, "answer": "42", "verdict": "too_low" }] }"#
+                    .to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            let submission = answers.data.first().unwrap();
+            assert_eq!(submission.day, day!(1));
+            assert_eq!(submission.part, 2);
+            assert_eq!(submission.answer, "42");
+            assert_eq!(submission.verdict, Verdict::TooLow);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_verdicts() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part": 2, "answer": "42", "verdict": "maybe" }] }"#
+                    .to_string();
+            Answers::try_from(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_invalid_parts() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part": 3, "answer": "42", "verdict": "correct" }] }"#
+                    .to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod serialization {
+        use super::get_mock_answers;
+        use crate::template::answers::Answers;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn round_trips_answers() {
+            let answers = get_mock_answers();
+            let json = JsonValue::from(answers.clone()).stringify().unwrap();
+            let parsed = Answers::try_from(json).unwrap();
+            assert_eq!(parsed.data, answers.data);
+        }
+    }
+
+    mod check {
+        use super::get_mock_answers;
+        use crate::{
+            day,
+            template::answers::{AnswerCheck, Verdict},
+            template::aoc_client::{Hint, SubmissionOutcome},
+        };
+
+        #[test]
+        fn allows_unknown_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(answers.check(day!(1), 1, "42"), AnswerCheck::Unknown);
+            assert_eq!(answers.check(day!(1), 2, "100"), AnswerCheck::Unknown);
+        }
+
+        #[test]
+        fn refuses_known_wrong_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.check(day!(1), 1, "50"),
+                AnswerCheck::KnownWrong(Verdict::Incorrect)
+            );
+        }
+
+        #[test]
+        fn detects_contradicted_bounds() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.check(day!(1), 1, "120"),
+                AnswerCheck::ContradictsBound {
+                    bound: "100".into(),
+                    verdict: Verdict::TooHigh
+                }
+            );
+            assert_eq!(
+                answers.check(day!(1), 1, "-3"),
+                AnswerCheck::ContradictsBound {
+                    bound: "10".into(),
+                    verdict: Verdict::TooLow
+                }
+            );
+        }
+
+        #[test]
+        fn marks_solved_parts() {
+            let mut answers = get_mock_answers();
+            assert!(answers.is_solved(day!(2), 1));
+            assert!(!answers.is_solved(day!(1), 1));
+
+            assert!(answers.record(day!(1), 1, "42", &SubmissionOutcome::Correct));
+            assert_eq!(
+                answers.check(day!(1), 1, "43"),
+                AnswerCheck::Solved("42".into())
+            );
+            assert_eq!(answers.correct_answer(day!(1), 1), Some("42"));
+        }
+
+        #[test]
+        fn ignores_outcomes_without_verdict() {
+            let mut answers = get_mock_answers();
+            assert!(!answers.record(day!(1), 1, "42", &SubmissionOutcome::RateLimited(None)));
+            assert!(answers.record(
+                day!(1),
+                1,
+                "42",
+                &SubmissionOutcome::Incorrect(Some(Hint::TooLow))
+            ));
+            assert_eq!(
+                answers.check(day!(1), 1, "42"),
+                AnswerCheck::KnownWrong(Verdict::TooLow)
+            );
+        }
+    }
+}
@@ -0,0 +1,522 @@
+/// Minimal client for the Advent of Code website.
+///
+/// Authenticates with the session cookie and talks to `AOC_BASE_URL` (defaults to the live website),
+/// which allows pointing it at a local mock server.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::{data_dir, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+/// Upper bound for a whole request, so a stalled connection can not hang the runner.
+static REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotFound,
+    Http { status: u16, url: String },
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set the AOC_SESSION environment variable or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::YearNotFound => {
+                write!(f, "no year configured. Set the AOC_YEAR environment variable.")
+            }
+            AocClientError::Http { status, url } => {
+                write!(f, "request to \"{url}\" failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "file system error: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => AocClientError::Http {
+                status,
+                url: response.get_url().into(),
+            },
+            ureq::Error::Transport(transport) => AocClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// Whether an incorrect answer was too high or too low, if the website told us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The verdict for a submitted answer, parsed from the response page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    RateLimited(Option<Duration>),
+    AlreadyCompleted,
+    Unknown(String),
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "⭐️ That's the right answer!"),
+            SubmissionOutcome::Incorrect(None) => write!(f, "✖ That's not the right answer."),
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh)) => {
+                write!(f, "✖ That's not the right answer, your answer is too high.")
+            }
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow)) => {
+                write!(f, "✖ That's not the right answer, your answer is too low.")
+            }
+            SubmissionOutcome::RateLimited(Some(wait)) => write!(
+                f,
+                "⏳ You gave an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            SubmissionOutcome::RateLimited(None) => {
+                write!(f, "⏳ You gave an answer too recently.")
+            }
+            SubmissionOutcome::AlreadyCompleted => {
+                write!(f, "This part is not open or has already been completed.")
+            }
+            SubmissionOutcome::Unknown(text) => write!(f, "Unexpected response: {text}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(REQUEST_TIMEOUT)
+                .build(),
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+        }
+    }
+
+    /// Creates a client from `AOC_SESSION` (or the session file) and `AOC_BASE_URL`.
+    /// If `year` is [`None`], the default year configured by `AOC_YEAR` is used.
+    pub fn from_env(year: Option<Year>) -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+        let year = year
+            .or_else(Year::from_env)
+            .ok_or(AocClientError::YearNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    /// Fetches the puzzle input for a day.
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetches the puzzle description for a day. Returns the HTML of all unlocked parts.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(day))?;
+        Ok(extract_articles(&page).join("\n\n"))
+    }
+
+    /// Submits an answer and parses the verdict from the response.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let page = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let text = extract_articles(&page)
+            .first()
+            .map_or_else(|| html_to_text(&page), |article| html_to_text(article));
+
+        Ok(parse_submission(&text))
+    }
+
+    /// Downloads input and puzzle description for a day and writes them to the data folder.
+    pub fn download(&self, day: Day) -> Result<(), AocClientError> {
+        let input_path = self.get_data_path("inputs", &format!("{day}.txt"))?;
+        let puzzle_path = self.get_data_path("puzzles", &format!("{day}.md"))?;
+
+        fs::write(&input_path, self.fetch_input(day)?)?;
+        fs::write(&puzzle_path, self.fetch_puzzle(day)?)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
+    }
+
+    /// Downloads the puzzle description for a day, writes it to the data folder and returns it as plain text.
+    pub fn read(&self, day: Day) -> Result<String, AocClientError> {
+        let puzzle = self.fetch_puzzle(day)?;
+        fs::write(
+            self.get_data_path("puzzles", &format!("{day}.md"))?,
+            &puzzle,
+        )?;
+        Ok(html_to_text(&puzzle))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    /// Path of a file in the data folder of the client's year. Creates missing folders.
+    fn get_data_path(&self, folder: &str, file_name: &str) -> Result<PathBuf, AocClientError> {
+        let dir = data_dir(Some(self.year)).join(folder);
+        fs::create_dir_all(&dir)?;
+        Ok(dir.join(file_name))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let config_dir = env::var("XDG_CONFIG_HOME").unwrap_or_else(|_| format!("{home}/.config"));
+
+    [
+        format!("{home}/{SESSION_FILE_NAME}"),
+        format!("{config_dir}/adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the contents of all `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    articles
+}
+
+/// Renders a HTML fragment as plain text, keeping line breaks of block elements.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (tag.starts_with('/'), name) {
+            (true, "p" | "pre" | "h2" | "ul") => text.push_str("\n\n"),
+            (false, "li") => text.push_str("\n - "),
+            (false, "br") => text.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    // collapse blank lines produced by nested block elements.
+    let mut text = decode_entities(&text);
+    while text.contains("\n\n\n") {
+        text = text.replace("\n\n\n", "\n\n");
+    }
+
+    text.trim().to_string()
+}
+
+pub(super) fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_submission(text: &str) -> SubmissionOutcome {
+    if text.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        SubmissionOutcome::Incorrect(hint)
+    } else if text.contains("You gave an answer too recently") {
+        SubmissionOutcome::RateLimited(parse_wait_time(text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::AlreadyCompleted
+    } else {
+        SubmissionOutcome::Unknown(text.into())
+    }
+}
+
+/// Parses wait times like `You have 1m 5s left to wait.`
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let unit = part.chars().next_back()?;
+            let value: u64 = part.strip_suffix(unit)?.parse().ok()?;
+            match unit {
+                'h' => Some(value * 3600),
+                'm' => Some(value * 60),
+                's' => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::AocClient;
+    use crate::year;
+
+    /// Serves the given `(status, body)` responses in order, one per connection.
+    /// Joining the returned handle yields the raw requests that were received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (AocClient, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = len.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body_buf = vec![0; content_length];
+                    reader.read_exact(&mut body_buf).unwrap();
+                    request.push_str(&String::from_utf8(body_buf).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (AocClient::new(&base_url, "secret", year!(2024)), handle)
+    }
+
+    mod requests {
+        use super::serve;
+        use crate::day;
+
+        #[test]
+        fn fetches_input_with_session() {
+            let (client, server) = serve(vec![(200, "1 2\n3 4\n")]);
+            let input = client.fetch_input(day!(1)).unwrap();
+            let requests = server.join().unwrap();
+
+            assert_eq!(input, "1 2\n3 4\n");
+            assert!(requests[0].starts_with("GET /2024/day/1/input "));
+            assert!(requests[0].contains("session=secret"));
+        }
+
+        #[test]
+        fn fetches_puzzle_articles() {
+            let (client, server) = serve(vec![(
+                200,
+                "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2></article><p>x</p><article class=\"day-desc\"><p>Part two</p></article></main>",
+            )]);
+            let puzzle = client.fetch_puzzle(day!(1)).unwrap();
+            server.join().unwrap();
+
+            assert_eq!(puzzle, "<h2>--- Day 1 ---</h2>\n\n<p>Part two</p>");
+        }
+
+        #[test]
+        fn submits_answer_as_form() {
+            let (client, server) = serve(vec![(
+                200,
+                "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>",
+            )]);
+            let outcome = client.submit(day!(3), 2, "42").unwrap();
+            let requests = server.join().unwrap();
+
+            assert_eq!(outcome, super::super::SubmissionOutcome::Correct);
+            assert!(requests[0].starts_with("POST /2024/day/3/answer "));
+            assert!(requests[0].ends_with("level=2&answer=42"));
+        }
+
+        #[test]
+        fn surfaces_http_errors() {
+            let (client, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+            let err = client.fetch_input(day!(1)).unwrap_err();
+            server.join().unwrap();
+
+            assert!(matches!(
+                err,
+                super::super::AocClientError::Http { status: 400, .. }
+            ));
+        }
+    }
+
+    mod parsing {
+        use std::time::Duration;
+
+        use crate::template::aoc_client::{
+            html_to_text, parse_submission, Hint, SubmissionOutcome,
+        };
+
+        #[test]
+        fn parses_incorrect_answers_with_hints() {
+            assert_eq!(
+                parse_submission("That's not the right answer; your answer is too high."),
+                SubmissionOutcome::Incorrect(Some(Hint::TooHigh))
+            );
+            assert_eq!(
+                parse_submission("That's not the right answer; your answer is too low."),
+                SubmissionOutcome::Incorrect(Some(Hint::TooLow))
+            );
+            assert_eq!(
+                parse_submission("That's not the right answer. If you're stuck, ..."),
+                SubmissionOutcome::Incorrect(None)
+            );
+        }
+
+        #[test]
+        fn parses_rate_limits() {
+            assert_eq!(
+                parse_submission("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+                SubmissionOutcome::RateLimited(Some(Duration::from_secs(65)))
+            );
+            assert_eq!(
+                parse_submission("You gave an answer too recently. You have 30s left to wait."),
+                SubmissionOutcome::RateLimited(Some(Duration::from_secs(30)))
+            );
+            assert_eq!(
+                parse_submission("You gave an answer too recently. You have 5€ left to wait."),
+                SubmissionOutcome::RateLimited(None)
+            );
+        }
+
+        #[test]
+        fn parses_completed_levels() {
+            assert_eq!(
+                parse_submission(
+                    "You don't seem to be solving the right level.  Did you already complete it?"
+                ),
+                SubmissionOutcome::AlreadyCompleted
+            );
+        }
+
+        #[test]
+        fn renders_html_as_text() {
+            assert_eq!(
+                html_to_text(
+                    "<h2>--- Day 1 ---</h2><p>A &lt;b&gt; <em>c</em></p><ul><li>d</li></ul>"
+                ),
+                "--- Day 1 ---\n\nA <b> c\n\n - d"
+            );
+        }
+    }
+}
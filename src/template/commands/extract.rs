@@ -0,0 +1,107 @@
+use std::{fs, path::Path, process};
+
+use crate::template::puzzle::{
+    code_blocks, guess_example_answers, guess_example_block, set_test_expectation,
+};
+use crate::template::{bin_name, data_dir, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub fn handle(day: Day, year: Option<Year>, list: bool, block: Option<usize>, overwrite: bool) {
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Failed to read puzzle \"{}\". Run `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let blocks = code_blocks(&puzzle);
+    let guess = guess_example_block(&blocks);
+
+    if list {
+        for (i, block) in blocks.iter().enumerate() {
+            let marker = if Some(i) == guess { " (example)" } else { "" };
+            println!("{ANSI_BOLD}Block {i}{ANSI_RESET}{ANSI_ITALIC}{marker}{ANSI_RESET}");
+            println!("{}\n", block.text.trim_end());
+        }
+        return;
+    }
+
+    let Some(index) = block.or(guess) else {
+        eprintln!("The puzzle does not contain any code blocks.");
+        process::exit(1);
+    };
+
+    let Some(example) = blocks.get(index) else {
+        eprintln!(
+            "Block {index} does not exist, the puzzle has {} blocks.",
+            blocks.len()
+        );
+        process::exit(1);
+    };
+
+    write_example(year, day, &example.text, overwrite);
+    write_expectations(year, day, &guess_example_answers(&puzzle));
+}
+
+/// Writes the example input. Existing examples are only replaced if empty or with `--overwrite`.
+fn write_example(year: Option<Year>, day: Day, text: &str, overwrite: bool) {
+    let example_path = data_dir(year).join("examples").join(format!("{day}.txt"));
+
+    let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+    if !is_empty && !overwrite {
+        eprintln!(
+            "Example file \"{}\" is not empty, pass `--overwrite` to replace it.",
+            example_path.display()
+        );
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::create_dir_all(example_path.parent().unwrap())
+        .and_then(|()| fs::write(&example_path, text))
+    {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+
+    println!("Wrote example to \"{}\"", example_path.display());
+}
+
+/// Sets the expected answers of the scaffolded tests, if they have not been changed yet.
+fn write_expectations(year: Option<Year>, day: Day, answers: &[Option<String>; 2]) {
+    let module_path = Path::new("src/bin").join(format!("{}.rs", bin_name(year, day)));
+
+    let Ok(mut source) = fs::read_to_string(&module_path) else {
+        println!(
+            "No module file \"{}\", skipping tests.",
+            module_path.display()
+        );
+        return;
+    };
+
+    let mut changed = false;
+
+    for (part, answer) in (1..=2).zip(answers) {
+        let Some(answer) = answer else {
+            println!("Part {part}: no example answer found.");
+            continue;
+        };
+
+        match set_test_expectation(&source, part, answer) {
+            Some(updated) => {
+                println!("Part {part}: expecting {ANSI_BOLD}{answer}{ANSI_RESET} in test.");
+                source = updated;
+                changed = true;
+            }
+            None => println!("Part {part}: found {answer}, test was already changed."),
+        }
+    }
+
+    if changed {
+        if let Err(e) = fs::write(&module_path, source) {
+            eprintln!("Failed to write module file: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,15 +1,23 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::puzzle;
+use crate::template::{bin_name, data_dir, is_default_year, Day, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory holding the module templates, e.g. `templates/grid.txt`.
+const TEMPLATES_DIR: &str = "templates";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+/// The default template is embedded so that scaffolding works without a templates directory.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +27,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +39,80 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Reads a module template from the templates directory. [`None`] selects the default template.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let path = Path::new(TEMPLATES_DIR).join(format!("{}.txt", name.unwrap_or("default")));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name.is_none() => Ok(DEFAULT_TEMPLATE.to_string()),
+        Err(e) => Err(format!(
+            "Failed to read template \"{}\": {e}. Available templates: {}",
+            path.display(),
+            list_templates().join(", ")
+        )),
+    }
+}
+
+fn list_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "txt").then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Replaces the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `7`.
+///  - `%DAY%`: the padded day, e.g. `07`.
+///  - `%YEAR%`: the year of the puzzle, e.g. `2024`.
+///  - `%YEAR_ARGUMENT%`: the `year` argument of `solution!`, empty for the default year.
+///  - `%TITLE%`: the puzzle title if the puzzle was downloaded, e.g. `Bridge Repair`, otherwise `Day 7`.
+fn render_template(template: &str, day: Day, year: Option<Year>) -> String {
+    // solutions of other years than the default year need to know their year.
+    let year_argument = match year {
+        Some(year) if !is_default_year(Some(year)) => format!(", year = {year}"),
+        _ => String::new(),
+    };
+
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    let title = fs::read_to_string(puzzle_path)
+        .ok()
+        .and_then(|puzzle| puzzle::title(&puzzle))
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    let year = year
+        .or_else(Year::from_env)
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR_ARGUMENT%", &year_argument)
+        .replace("%YEAR%", &year)
+        .replace("%TITLE%", &title)
+}
+
+pub fn handle(day: Day, year: Option<Year>, overwrite: bool, template: Option<&str>) {
+    let data_dir = data_dir(year);
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let module_path = Path::new("src/bin").join(format!("{}.rs", bin_name(year, day)));
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +122,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render_template(&template, day, year).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -56,7 +134,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +144,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +153,10 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year {
+        Some(year) if !is_default_year(Some(year)) => {
+            println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+        }
+        _ => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
@@ -1,34 +1,187 @@
-use std::process::{Command, Stdio};
+use std::{
+    io::{stdout, Write},
+    path::Path,
+    process::{self, Command, Stdio},
+    time::Duration,
+};
 
-use crate::template::Day;
+use crate::template::watch::Watcher;
+use crate::template::{bin_name, data_dir, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// How often watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Options for visualizing a solution, see [`crate::visualize`].
+#[derive(Clone, Debug, Default)]
+pub struct VisualizeOptions {
+    /// Play the frames as a terminal animation.
+    pub play: bool,
+    /// Write the frames to a `.gif` or text file.
+    pub export: Option<String>,
+    /// Initial playback speed in frames per second.
+    pub fps: Option<u32>,
+}
+
+impl VisualizeOptions {
+    fn is_enabled(&self) -> bool {
+        self.play || self.export.is_some()
+    }
+
+    /// Arguments forwarded to the solution binary.
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.play {
+            args.push("--visualize".to_string());
+        }
+        if let Some(path) = &self.export {
+            args.extend(["--export".to_string(), path.clone()]);
+        }
+        if let Some(fps) = self.fps {
+            args.extend(["--fps".to_string(), fps.to_string()]);
+        }
+        args
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    year: Option<Year>,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    examples: bool,
+    watch: bool,
+    visualize: &VisualizeOptions,
+) {
+    if !watch {
+        run(&mut solve_command(
+            year,
+            day,
+            release,
+            dhat,
+            submit_part,
+            examples,
+            visualize,
+        ));
+        return;
+    }
+
+    // re-running would submit the answer on every change.
+    if submit_part.is_some() {
+        eprintln!("`--watch` can not be combined with `--submit`.");
+        process::exit(1);
+    }
+
+    watch_day(year, day, release, dhat, examples, visualize);
+}
+
+/// Re-run the tests and the solution of a day whenever its source or data files change.
+fn watch_day(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    examples: bool,
+    visualize: &VisualizeOptions,
+) {
+    let bin = bin_name(year, day);
+    let data_dir = data_dir(year);
+
+    let mut watcher = Watcher::default()
+        .file(Path::new("src/bin").join(format!("{bin}.rs")))
+        .file("src/lib.rs")
+        .file(data_dir.join("inputs").join(format!("{day}.txt")))
+        .files_in(data_dir.join("examples"), &day.to_string());
+
+    loop {
+        watcher.poll();
+
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+        println!("------");
+        let tests_passed = run(&mut test_command(&bin, release));
+
+        if tests_passed {
+            println!("\n{ANSI_BOLD}Solution{ANSI_RESET}");
+            println!("------");
+            run(&mut solve_command(
+                year, day, release, dhat, None, examples, visualize,
+            ));
+        }
+
+        println!(
+            "\n{ANSI_ITALIC}Watching {} files for changes, press Ctrl-C to stop.{ANSI_RESET}",
+            watcher.len()
+        );
+        let _ = stdout().flush();
+
+        watcher.wait_for_change(POLL_INTERVAL);
+    }
+}
+
+fn solve_command(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    examples: bool,
+    visualize: &VisualizeOptions,
+) -> Command {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
+    let mut features = vec![];
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if visualize.is_enabled() {
+        features.push("visualize");
+    }
+
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
     cmd_args.push("--".to_string());
+    cmd_args.extend(visualize.args());
+
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+    cmd
+}
+
+fn test_command(bin: &str, release: bool) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--quiet", "--bin", bin]);
+    if release {
+        cmd.arg("--release");
+    }
+    cmd
+}
+
+/// Run a command with inherited output. Returns whether it succeeded.
+fn run(cmd: &mut Command) -> bool {
+    let mut child = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    child.wait().unwrap().success()
 }
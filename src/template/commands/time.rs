@@ -1,15 +1,30 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::history::{current_commit, current_timestamp, Comparison, History};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, is_default_year, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Option<Day>,
+    year: Option<Year>,
+    run_all: bool,
+    store: bool,
+    budget: Option<u64>,
+    compare: bool,
+    baseline: Option<String>,
+    jobs: usize,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,14 +36,35 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, year, true, true, budget, jobs).unwrap();
+
+    let mut history = History::read_from_file(year);
+
+    let regressions = if compare {
+        print_comparison(&history, &timings, baseline.as_deref())
+    } else {
+        0
+    };
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        history.append(&current_commit(), current_timestamp(), &timings);
+        if history.store_file(year).is_err() {
+            eprintln!("Failed to store benchmark history.");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+
+        // the readme only tracks benchmarks of the default year.
+        let result = if is_default_year(year) {
+            readme_benchmarks::update(merged_timings)
+        } else {
+            Ok(())
+        };
+
+        match result {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +73,60 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} significant regression(s) found.");
+        process::exit(1);
+    }
+}
+
+/// Print per-part deltas against the baseline in the history. Returns the number of regressions.
+fn print_comparison(history: &History, timings: &Timings, baseline: Option<&str>) -> usize {
+    let mut regressions = 0;
+
+    println!("\n{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    for timing in &timings.data {
+        let Some(entry) = history.baseline(timing.day, baseline) else {
+            println!("Day {}: no baseline", timing.day);
+            continue;
+        };
+
+        println!("Day {} (vs. {})", timing.day, entry.commit);
+
+        let parts = [
+            (1, &entry.timing.part_1_stats, &timing.part_1_stats),
+            (2, &entry.timing.part_2_stats, &timing.part_2_stats),
+        ];
+
+        for (part, old, new) in parts {
+            let (Some(old), Some(new)) = (old, new) else {
+                println!("Part {part}: no baseline");
+                continue;
+            };
+
+            let comparison = Comparison::new(old, new);
+            let marker = if comparison.is_regression() {
+                regressions += 1;
+                " ✘ regression"
+            } else {
+                ""
+            };
+
+            println!(
+                "Part {part}: {:.1?} → {:.1?} ({:+.1}%){marker}",
+                to_duration(old.mean_nanos),
+                to_duration(new.mean_nanos),
+                comparison.delta * 100.0
+            );
+        }
+    }
+
+    regressions
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos as u64)
 }
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{child_commands, for_each_day};
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// The result of comparing a part's output against the recorded correct answer.
+enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        found: Option<String>,
+    },
+    Missing,
+}
+
+pub fn handle(day: Option<Day>, year: Option<Year>, is_release: bool, jobs: usize) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read the answer ledger: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = 0;
+
+    for_each_day(
+        &days_to_run,
+        year,
+        is_release,
+        false,
+        None,
+        jobs,
+        |day, output| {
+            let results = child_commands::parse_answers(output);
+
+            for (part, found) in (1..=2).zip(results) {
+                // a day without reports has not run at all, only parts with a recorded answer matter.
+                if output.is_empty() && answers.correct_answer(day, part).is_none() {
+                    continue;
+                }
+
+                match verify_part(&answers, day, part, found) {
+                    Status::Pass => {
+                        passed += 1;
+                        println!("Verify part {part}: ✔");
+                    }
+                    Status::Fail { expected, found } => {
+                        failed += 1;
+                        let found = found.unwrap_or_else(|| "no result".into());
+                        println!("Verify part {part}: ✘ expected {expected}, found {found}");
+                    }
+                    Status::Missing => {
+                        missing += 1;
+                        println!("Verify part {part}: no recorded answer");
+                    }
+                }
+            }
+        },
+    );
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing"
+    );
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify_part(answers: &Answers, day: Day, part: u8, found: Option<String>) -> Status {
+    match answers.correct_answer(day, part) {
+        None => Status::Missing,
+        Some(expected) if found.as_deref() == Some(expected) => Status::Pass,
+        Some(expected) => Status::Fail {
+            expected: expected.to_string(),
+            found,
+        },
+    }
+}
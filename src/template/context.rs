@@ -0,0 +1,219 @@
+use std::{collections::HashMap, fmt::Debug, str::FromStr};
+
+use crate::template::examples::Example;
+
+/// The kind of input a solution part is run against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// The real puzzle input.
+    Real,
+    /// A named example input.
+    Example(String),
+}
+
+/// Context passed to solution parts that accept a second `&Context` argument.
+///
+/// Some puzzles use different parameters for the examples and the real input, e.g. grid sizes or thresholds.
+/// Examples can set these parameters in the `params` object of their entry in the example manifest.
+/// For the real input, parts fall back to their defaults.
+///
+/// ```
+/// # use advent_of_code::template::Context;
+/// fn part_one(input: &str, ctx: &Context) -> Option<u32> {
+///     let width: u32 = ctx.param_or("width", 101);
+///     Some(width)
+/// }
+///
+/// assert_eq!(part_one("", &Context::real()), Some(101));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Context {
+    pub kind: InputKind,
+    params: HashMap<String, String>,
+}
+
+impl Context {
+    /// Context for the real puzzle input.
+    pub fn real() -> Self {
+        Context {
+            kind: InputKind::Real,
+            params: HashMap::new(),
+        }
+    }
+
+    /// Context for an example input, carrying the parameters of its manifest entry.
+    pub fn example(example: &Example) -> Self {
+        Context {
+            kind: InputKind::Example(example.name.clone()),
+            params: example.params.clone(),
+        }
+    }
+
+    pub fn is_example(&self) -> bool {
+        matches!(self.kind, InputKind::Example(_))
+    }
+
+    /// Returns the parameter `key` parsed as `T`, or [`None`] if it is not set.
+    ///
+    /// # Panics
+    /// Panics if the parameter is set but can not be parsed as `T`.
+    pub fn param<T: FromStr>(&self, key: &str) -> Option<T>
+    where
+        T::Err: Debug,
+    {
+        self.params.get(key).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid value `{value}` for parameter `{key}`: {e:?}"))
+        })
+    }
+
+    /// Returns the parameter `key` parsed as `T`, or `default` if it is not set.
+    ///
+    /// # Panics
+    /// Panics if the parameter is set but can not be parsed as `T`.
+    pub fn param_or<T: FromStr>(&self, key: &str, default: T) -> T
+    where
+        T::Err: Debug,
+    {
+        self.param(key).unwrap_or(default)
+    }
+}
+
+/// A solution part, either `fn(&I) -> R` or `fn(&I, &Context) -> R`.
+///
+/// `I` is `str` for parts that take the raw input and the output of `parse` for solutions with a shared parse step.
+/// `R` is the return type of the part, e.g. `Option<T>` or `Result<T, E>`.
+/// `M` is a marker that keeps the two implementations apart, it is inferred from the function signature.
+pub trait Part<M, I: ?Sized, R> {
+    fn call(&self, input: &I, ctx: &Context) -> R;
+}
+
+/// The shared parse step of a solution, either `fn(&str) -> P` or `fn(&str, &Context) -> P`.
+///
+/// The parsed input can not borrow from the raw input.
+pub trait Parse<M, P> {
+    fn call(&self, input: &str, ctx: &Context) -> P;
+}
+
+/// Marker for parts that ignore the [`Context`].
+pub struct WithoutContext;
+
+/// Marker for parts that take a [`Context`].
+pub struct WithContext;
+
+impl<F, I: ?Sized, R> Part<WithoutContext, I, R> for F
+where
+    F: Fn(&I) -> R,
+{
+    fn call(&self, input: &I, _: &Context) -> R {
+        self(input)
+    }
+}
+
+impl<F, I: ?Sized, R> Part<WithContext, I, R> for F
+where
+    F: Fn(&I, &Context) -> R,
+{
+    fn call(&self, input: &I, ctx: &Context) -> R {
+        self(input, ctx)
+    }
+}
+
+impl<F, P> Parse<WithoutContext, P> for F
+where
+    F: Fn(&str) -> P,
+{
+    fn call(&self, input: &str, _: &Context) -> P {
+        self(input)
+    }
+}
+
+impl<F, P> Parse<WithContext, P> for F
+where
+    F: Fn(&str, &Context) -> P,
+{
+    fn call(&self, input: &str, ctx: &Context) -> P {
+        self(input, ctx)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Context, InputKind, Parse, Part};
+    use crate::template::examples::Example;
+
+    fn get_mock_example() -> Example {
+        Example {
+            name: "small".into(),
+            file: "01-small.txt".into(),
+            part_1: None,
+            part_2: None,
+            params: HashMap::from([("width".into(), "11".into()), ("name".into(), "abc".into())]),
+        }
+    }
+
+    #[test]
+    fn reads_example_params() {
+        let ctx = Context::example(&get_mock_example());
+
+        assert!(ctx.is_example());
+        assert_eq!(ctx.kind, InputKind::Example("small".into()));
+        assert_eq!(ctx.param::<u32>("width"), Some(11));
+        assert_eq!(ctx.param_or::<u32>("height", 103), 103);
+        assert_eq!(ctx.param::<String>("name"), Some("abc".into()));
+    }
+
+    #[test]
+    fn falls_back_to_defaults_for_real_input() {
+        let ctx = Context::real();
+
+        assert!(!ctx.is_example());
+        assert_eq!(ctx.param_or("width", 101), 101);
+    }
+
+    #[test]
+    fn calls_parts_with_and_without_context() {
+        fn without(input: &str) -> Option<usize> {
+            Some(input.len())
+        }
+
+        fn with(input: &str, ctx: &Context) -> Option<usize> {
+            Some(input.len() * ctx.param_or("factor", 1))
+        }
+
+        let ctx = Context::example(&get_mock_example());
+        assert_eq!(Part::call(&without, "abc", &ctx), Some(3));
+        assert_eq!(Part::call(&with, "abc", &Context::real()), Some(3));
+    }
+
+    #[test]
+    fn calls_parsed_parts() {
+        fn parse(input: &str, ctx: &Context) -> Vec<u32> {
+            let offset: u32 = ctx.param_or("offset", 0);
+            input
+                .bytes()
+                .map(|b| u32::from(b - b'0') + offset)
+                .collect()
+        }
+
+        fn sum(input: &Vec<u32>) -> Option<u32> {
+            Some(input.iter().sum())
+        }
+
+        let ctx = Context::real();
+        let parsed = Parse::call(&parse, "123", &ctx);
+        assert_eq!(Part::call(&sum, &parsed, &ctx), Some(6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_params() {
+        let ctx = Context::example(&get_mock_example());
+        ctx.param::<u32>("name");
+    }
+}
@@ -0,0 +1,262 @@
+/// Named example inputs of a day, with optional expected answers.
+///
+/// Examples are listed in a manifest at `data/examples/{day}.json`:
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "name": "small", "part_1": "11", "part_2": 31 },
+///     { "name": "larger", "file": "01-larger.txt", "part_1": null, "params": { "width": 11 } }
+///   ]
+/// }
+/// ```
+///
+/// The input of an example is read from `file` in the examples folder, defaulting to `{day}-{name}.txt`.
+/// The optional `params` object sets puzzle parameters that are passed to solutions via `Context`.
+/// Days without a manifest have a single `default` example read from `{day}.txt`.
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Context, Day, Year};
+
+/// A named example input of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    /// File name of the input, relative to the examples folder.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Puzzle parameters passed to solutions, see [`Context`].
+    pub params: HashMap<String, String>,
+}
+
+impl Example {
+    /// The example read from `{day}.txt`, used when a day has no manifest.
+    pub fn default_for(day: Day) -> Self {
+        Example {
+            name: "default".into(),
+            file: format!("{day}.txt"),
+            part_1: None,
+            part_2: None,
+            params: HashMap::new(),
+        }
+    }
+
+    /// The expected answer of a part, if known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Context for running solutions against the example.
+    pub fn context(&self) -> Context {
+        Context::example(self)
+    }
+
+    /// Reads the input of the example. [`None`] selects the default year.
+    pub fn read_input(&self, year: Option<Year>) -> Result<String, String> {
+        let path = data_dir(year).join("examples").join(&self.file);
+        fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+/// All examples of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a day. [`None`] selects the default year.
+    /// If the day has no manifest, returns a manifest with the default example.
+    pub fn read(year: Option<Year>, day: Day) -> Result<Self, String> {
+        let path = data_dir(year).join("examples").join(format!("{day}.json"));
+
+        if !path.exists() {
+            return Ok(Manifest {
+                examples: vec![Example::default_for(day)],
+            });
+        }
+
+        let contents = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        Manifest::parse(&contents, day).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Parses a manifest. `day` is used to derive default file names.
+    pub fn parse(s: &str, day: Day) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json_examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(Manifest {
+            examples: json_examples
+                .iter()
+                .map(|example| parse_example(example, day))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Returns the example with the given name.
+    pub fn example(&self, name: &str) -> Option<&Example> {
+        self.examples.iter().find(|example| example.name == name)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_example(value: &JsonValue, day: Day) -> Result<Example, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected example to be a JSON object.")?;
+
+    let name = json
+        .get("name")
+        .and_then(|v| v.get::<String>())
+        .ok_or("Expected example.name to be a string.")?;
+
+    let file = match json.get("file") {
+        None => format!("{day}-{name}.txt"),
+        Some(v) => v
+            .get::<String>()
+            .cloned()
+            .ok_or("Expected example.file to be a string.")?,
+    };
+
+    let params = match json.get("params") {
+        None => HashMap::new(),
+        Some(v) => v
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example.params to be an object.")?
+            .iter()
+            .map(|(key, value)| match parse_answer(Some(value)) {
+                Some(Some(value)) => Ok((key.clone(), value)),
+                _ => Err(format!(
+                    "Expected example.params.{key} to be a string or an integer below 2^53."
+                )),
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    Ok(Example {
+        name: name.clone(),
+        file,
+        params,
+        part_1: parse_answer(json.get("part_1"))
+            .ok_or("Expected example.part_1 to be null, a string or an integer below 2^53.")?,
+        part_2: parse_answer(json.get("part_2"))
+            .ok_or("Expected example.part_2 to be null, a string or an integer below 2^53.")?,
+    })
+}
+
+/// Largest integer that JSON numbers, which are parsed as `f64`, represent exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Expected answers and parameters can be strings or integers. Returns [`None`] for invalid values.
+///
+/// Numbers are rejected unless they are integers below 2^53, larger answers have to be quoted.
+fn parse_answer(value: Option<&JsonValue>) -> Option<Option<String>> {
+    match value {
+        None | Some(JsonValue::Null) => Some(None),
+        Some(JsonValue::String(s)) => Some(Some(s.clone())),
+        Some(JsonValue::Number(n)) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER =>
+        {
+            #[allow(clippy::cast_possible_truncation)]
+            Some(Some((*n as i64).to_string()))
+        }
+        Some(_) => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Manifest};
+    use crate::day;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parses_manifests() {
+        let json = r#"{ "examples": [
+            { "name": "small", "part_1": "11", "part_2": 31 },
+            { "name": "big", "file": "big.txt", "part_1": "9007199254740993" },
+            { "name": "larger", "file": "other.txt", "part_1": null, "params": { "width": 11, "mode": "fast" } }
+        ] }"#;
+        let manifest = Manifest::parse(json, day!(1)).unwrap();
+
+        assert_eq!(
+            manifest.examples,
+            vec![
+                Example {
+                    name: "small".into(),
+                    file: "01-small.txt".into(),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                    params: HashMap::new(),
+                },
+                Example {
+                    name: "big".into(),
+                    file: "big.txt".into(),
+                    part_1: Some("9007199254740993".into()),
+                    part_2: None,
+                    params: HashMap::new(),
+                },
+                Example {
+                    name: "larger".into(),
+                    file: "other.txt".into(),
+                    part_1: None,
+                    part_2: None,
+                    params: HashMap::from([
+                        ("width".into(), "11".into()),
+                        ("mode".into(), "fast".into())
+                    ]),
+                },
+            ]
+        );
+        assert_eq!(manifest.example("small").unwrap().expected(2), Some("31"));
+        assert!(manifest.example("missing").is_none());
+    }
+
+    #[test]
+    fn errors_for_malformed_manifests() {
+        assert!(Manifest::parse("{}", day!(1)).is_err());
+        assert!(Manifest::parse(r#"{ "examples": [{ "part_1": 1 }] }"#, day!(1)).is_err());
+        assert!(Manifest::parse(
+            r#"{ "examples": [{ "name": "a", "part_1": [] }] }"#,
+            day!(1)
+        )
+        .is_err());
+        assert!(Manifest::parse(
+            r#"{ "examples": [{ "name": "a", "params": { "x": null } }] }"#,
+            day!(1)
+        )
+        .is_err());
+        assert!(Manifest::parse(
+            r#"{ "examples": [{ "name": "a", "part_1": 9007199254740993 }] }"#,
+            day!(1)
+        )
+        .is_err());
+        assert!(Manifest::parse(
+            r#"{ "examples": [{ "name": "a", "part_1": 1.5 }] }"#,
+            day!(1)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn defaults_to_day_file() {
+        let example = Example::default_for(day!(7));
+        assert_eq!(example.file, "07.txt");
+        assert_eq!(example.expected(1), None);
+    }
+}
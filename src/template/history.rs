@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::report::BenchStats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{data_dir, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// Minimum relative slowdown before a change is considered a regression.
+const REGRESSION_THRESHOLD: f64 = 0.05;
+
+/// Minimum value of Welch's t statistic before a change is considered significant.
+const SIGNIFICANCE_THRESHOLD: f64 = 3.0;
+
+/// Timings of a single day, recorded at a specific commit.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub commit: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub timing: Timing,
+}
+
+/// Append-only history of benchmark timings.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Dehydrate history of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(HISTORY_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate history of a year from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(History::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Append a set of timings recorded at `commit`.
+    pub fn append(&mut self, commit: &str, timestamp: u64, timings: &Timings) {
+        self.data
+            .extend(timings.data.iter().map(|timing| HistoryEntry {
+                commit: commit.to_string(),
+                timestamp,
+                timing: timing.clone(),
+            }));
+    }
+
+    /// Find the most recent entry for a day.
+    /// If `commit` is set, only entries whose commit starts with it are considered.
+    pub fn baseline(&self, day: Day, commit: Option<&str>) -> Option<&HistoryEntry> {
+        self.data
+            .iter()
+            .filter(|entry| entry.timing.day == day)
+            .filter(|entry| commit.is_none_or(|c| entry.commit.starts_with(c)))
+            .max_by_key(|entry| entry.timestamp)
+    }
+}
+
+/// Short hash of the checked out git commit, suffixed with `-dirty` if there are uncommitted changes.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{hash}-dirty"),
+            _ => hash,
+        },
+        None => "unknown".into(),
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of a part's timing relative to a baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    /// Relative change of the mean, e.g. `0.1` for 10% slower.
+    pub delta: f64,
+    /// Welch's t statistic, positive if the current run is slower.
+    pub t_statistic: f64,
+}
+
+impl Comparison {
+    pub fn new(baseline: &BenchStats, current: &BenchStats) -> Self {
+        // a relative change can not be computed against an empty baseline, treat it as no change.
+        if baseline.mean_nanos <= 0.0 {
+            return Comparison {
+                delta: 0.0,
+                t_statistic: 0.0,
+            };
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance =
+            |stats: &BenchStats| stats.std_dev_nanos.powi(2) / stats.samples.max(1) as f64;
+
+        let diff = current.mean_nanos - baseline.mean_nanos;
+        let std_err = (variance(baseline) + variance(current)).sqrt();
+
+        let t_statistic = if std_err > 0.0 {
+            diff / std_err
+        } else if diff == 0.0 {
+            0.0
+        } else {
+            diff.signum() * f64::INFINITY
+        };
+
+        Comparison {
+            delta: diff / baseline.mean_nanos,
+            t_statistic,
+        }
+    }
+
+    /// Whether the current run is significantly slower than the baseline.
+    pub fn is_regression(&self) -> bool {
+        self.delta > REGRESSION_THRESHOLD && self.t_statistic > SIGNIFICANCE_THRESHOLD
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("commit".into(), JsonValue::String(value.commit.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.commit to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected entry to have a timing.")
+            .map(Timing::try_from)??;
+
+        Ok(HistoryEntry {
+            commit: commit.clone(),
+            timestamp,
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::template::{
+        report::BenchStats,
+        timings::{Timing, Timings},
+        Day,
+    };
+
+    fn get_mock_timings(day: u8, total_nanos: f64) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: Day::new(day).unwrap(),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                total_nanos,
+            }],
+        }
+    }
+
+    fn get_mock_stats(mean_nanos: f64, std_dev_nanos: f64) -> BenchStats {
+        BenchStats {
+            samples: 100,
+            mean_nanos,
+            std_dev_nanos,
+            ..BenchStats::default()
+        }
+    }
+
+    mod history {
+        use super::get_mock_timings;
+        use crate::{day, template::history::History};
+
+        #[test]
+        fn appends_entries() {
+            let mut history = History::default();
+            history.append("abc", 1, &get_mock_timings(1, 1.0));
+            history.append("def", 2, &get_mock_timings(1, 2.0));
+
+            assert_eq!(history.data.len(), 2);
+            assert_eq!(history.data[0].commit, "abc");
+        }
+
+        #[test]
+        fn finds_baselines() {
+            let mut history = History::default();
+            history.append("abc123", 1, &get_mock_timings(1, 1.0));
+            history.append("def456", 2, &get_mock_timings(1, 2.0));
+            history.append("def456", 3, &get_mock_timings(2, 3.0));
+
+            let latest = history.baseline(day!(1), None).unwrap();
+            assert_eq!(latest.commit, "def456");
+
+            let pinned = history.baseline(day!(1), Some("abc")).unwrap();
+            assert_eq!(pinned.timing.total_nanos, 1.0);
+
+            assert!(history.baseline(day!(3), None).is_none());
+        }
+
+        #[test]
+        fn round_trips_json() {
+            let mut history = History::default();
+            history.append("abc", 1_700_000_000, &get_mock_timings(5, 42.0));
+
+            let json = tinyjson::JsonValue::from(history).stringify().unwrap();
+            let parsed = History::try_from(json).unwrap();
+
+            assert_eq!(parsed.data.len(), 1);
+            assert_eq!(parsed.data[0].commit, "abc");
+            assert_eq!(parsed.data[0].timestamp, 1_700_000_000);
+            assert_eq!(parsed.data[0].timing.day, day!(5));
+        }
+    }
+
+    mod comparison {
+        use super::get_mock_stats;
+        use crate::template::history::Comparison;
+
+        #[test]
+        fn flags_significant_regressions() {
+            let cmp = Comparison::new(&get_mock_stats(100.0, 5.0), &get_mock_stats(120.0, 5.0));
+            assert!((cmp.delta - 0.2).abs() < 1e-9);
+            assert!(cmp.is_regression());
+        }
+
+        #[test]
+        fn ignores_noisy_changes() {
+            let cmp = Comparison::new(&get_mock_stats(100.0, 200.0), &get_mock_stats(120.0, 200.0));
+            assert!(!cmp.is_regression());
+        }
+
+        #[test]
+        fn ignores_small_changes() {
+            let cmp = Comparison::new(&get_mock_stats(100.0, 0.0), &get_mock_stats(102.0, 0.0));
+            assert!(!cmp.is_regression());
+        }
+
+        #[test]
+        fn ignores_improvements() {
+            let cmp = Comparison::new(&get_mock_stats(100.0, 1.0), &get_mock_stats(50.0, 1.0));
+            assert!(cmp.delta < 0.0);
+            assert!(!cmp.is_regression());
+        }
+
+        #[test]
+        fn ignores_zero_baselines() {
+            let cmp = Comparison::new(&get_mock_stats(0.0, 0.0), &get_mock_stats(50.0, 1.0));
+            assert_eq!(cmp.delta, 0.0);
+            assert!(!cmp.is_regression());
+        }
+    }
+}
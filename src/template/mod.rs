@@ -1,68 +1,185 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
 pub mod runner;
 
+pub use answer::*;
+pub use context::*;
 pub use day::*;
+pub use year::*;
 
+mod answer;
+mod answers;
+mod context;
 mod day;
+mod history;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Directory holding the data files of a year. [`None`] selects the default year.
+///
+/// The default year uses `data` directly, other years use `data/{year}`.
+#[must_use]
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) if !year.is_default() => PathBuf::from("data").join(year.to_string()),
+        _ => PathBuf::from("data"),
+    }
+}
+
+/// Name of the solution binary of a day, e.g. `07` for the default year and `2023-07` for other years.
+#[must_use]
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) if !year.is_default() => format!("{year}-{day}"),
+        _ => day.to_string(),
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_file_for_year(folder, None, day)
+}
+
+/// Helper function that reads a text file of a year to a string. [`None`] selects the default year.
+#[must_use]
+pub fn read_file_for_year(folder: &str, year: Option<Year>, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a named example of a day, returning its input and [`Context`].
+///
+/// # Panics
+/// Panics if the manifest or the example input can not be read, or if the manifest has no example `name`.
+#[must_use]
+pub fn read_example(year: Option<Year>, day: Day, name: &str) -> (String, Context) {
+    let manifest = examples::Manifest::read(year, day).expect("could not read example manifest");
+    let example = manifest
+        .example(name)
+        .unwrap_or_else(|| panic!("no example named \"{name}\""));
+    let input = example
+        .read_input(year)
+        .expect("could not open example file");
+    (input, example.context())
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_file_part_for_year(folder, None, day, part)
+}
+
+/// Like [`read_file_part`], but reads the file of a year. [`None`] selects the default year.
+#[must_use]
+pub fn read_file_part_for_year(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(year))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `year = <year>` parameter sets the year of the solution, it defaults to the year configured by `AOC_YEAR`.
+///
+/// Parts are either `fn(&str) -> R` or `fn(&str, &Context) -> R`, see [`Context`].
+/// `R` can be `Option<T>`, `Result<T, E>` or [`Answer`], see [`IntoOutcome`].
+///
+/// With `parse = <fn>`, the input is parsed once by `fn(&str) -> Input` and both parts take `&Input` instead of `&str`.
+/// Parsing is timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:literal)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, year = $year:literal)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 2 $(, year = $year:literal)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, parse = $parse:expr $(, year = $year:literal)?) => {
+        $crate::solution!(@impl_parse $day, [$($year)?], $parse, [part_one, 1] [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($year:literal)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $day, [$($year)?]);
+
+        fn main() {
+            use $crate::template::runner::*;
+
+            if is_examples_mode() {
+                let mut results = ExampleResults::default();
+                for (example, input) in read_examples(YEAR, DAY) {
+                    print_example_header(&example);
+                    $( run_example_part($func, input.as_str(), &example, $part, &mut results); )*
+                }
+                results.finish();
+                return;
+            }
+
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            $( run_part($func, input.as_str(), YEAR, DAY, $part); )*
+        }
+    };
+
+    (@impl_parse $day:expr, [$($year:literal)?], $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $day, [$($year)?]);
+
+        fn main() {
+            use $crate::template::runner::*;
+
+            if is_examples_mode() {
+                let mut results = ExampleResults::default();
+                for (example, input) in read_examples(YEAR, DAY) {
+                    print_example_header(&example);
+                    let parsed = parse_example($parse, &input, &example);
+                    $( run_example_part($func, &parsed, &example, $part, &mut results); )*
+                }
+                results.finish();
+                return;
+            }
+
+            let input = $crate::template::read_file_for_year("inputs", YEAR, DAY);
+            let parsed = run_parse($parse, &input);
+            $( run_part($func, &parsed, YEAR, DAY, $part); )*
+        }
+    };
+
+    (@consts $day:expr, [$($year:literal)?]) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current year, [`None`] selects the default year.
+        const YEAR: Option<$crate::template::Year> = $crate::solution!(@year $($year)?);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
+
+    (@year) => { None };
+    (@year $year:literal) => { Some($crate::year!($year)) };
 }
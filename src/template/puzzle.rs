@@ -0,0 +1,234 @@
+/// Extracts example inputs and answers from puzzle descriptions written by `download` and `read`.
+///
+/// Puzzle descriptions are stored as the HTML of their `<article>` elements.
+/// Examples are the contents of `<pre><code>` blocks, answers to the examples are usually emphasized as `<code><em>`.
+use super::aoc_client::decode_entities;
+
+const PART_TWO_HEADING: &str = "<h2 id=\"part2\">";
+
+/// A `<pre><code>` block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Plain text of the block, with tags removed and entities decoded.
+    pub text: String,
+    /// Plain text of the description between the previous block and this one.
+    pub preceding_text: String,
+}
+
+impl CodeBlock {
+    /// Whether the block is introduced as an example, e.g. by "For example:".
+    pub fn is_introduced_as_example(&self) -> bool {
+        let text = self.preceding_text.to_lowercase();
+        text.contains("for example") || text.contains("example:")
+    }
+}
+
+/// Returns the title of a puzzle, e.g. `Historian Hysteria` for `--- Day 1: Historian Hysteria ---`.
+pub fn title(html: &str) -> Option<String> {
+    let start = html.find("<h2>--- Day ")? + "<h2>--- Day ".len();
+    let end = start + html[start..].find("</h2>")?;
+
+    let (_, title) = html[start..end].split_once(": ")?;
+    let title = decode_entities(title.trim_end_matches('-').trim());
+
+    (!title.is_empty()).then_some(title)
+}
+
+/// Returns all `<pre><code>` blocks of a puzzle description, in order.
+pub fn code_blocks(html: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        let content_start = start + "<pre><code>".len();
+        let Some(end) = rest[content_start..].find("</code></pre>") else {
+            break;
+        };
+
+        blocks.push(CodeBlock {
+            text: strip_tags(&rest[content_start..content_start + end]),
+            preceding_text: strip_tags(&rest[..start]),
+        });
+
+        rest = &rest[content_start + end..];
+    }
+
+    blocks
+}
+
+/// Guesses which block holds the example input of part one.
+///
+/// Picks the first block introduced with "For example", falling back to the first block spanning several lines.
+pub fn guess_example_block(blocks: &[CodeBlock]) -> Option<usize> {
+    blocks
+        .iter()
+        .position(CodeBlock::is_introduced_as_example)
+        .or_else(|| {
+            blocks
+                .iter()
+                .position(|block| block.text.trim().contains('\n'))
+        })
+        .or(if blocks.is_empty() { None } else { Some(0) })
+}
+
+/// Guesses the answers to the example of both parts.
+///
+/// The answer of a part is the last emphasized code (`<code><em>` or `<em><code>`) in its section of the description.
+/// Part two is only available once it has been unlocked.
+pub fn guess_example_answers(html: &str) -> [Option<String>; 2] {
+    let (part_one, part_two) = match html.find(PART_TWO_HEADING) {
+        Some(i) => (&html[..i], Some(&html[i..])),
+        None => (html, None),
+    };
+
+    [
+        last_emphasized_code(part_one),
+        part_two.and_then(last_emphasized_code),
+    ]
+}
+
+/// Updates the assertion of a scaffolded test `test_part_one` or `test_part_two` to expect `answer`.
+///
+/// Returns [`None`] if the test does not exist or was already changed from `assert_eq!(result, None);`.
+pub fn set_test_expectation(source: &str, part: u8, answer: &str) -> Option<String> {
+    let name = match part {
+        1 => "fn test_part_one()",
+        2 => "fn test_part_two()",
+        _ => return None,
+    };
+
+    let test_start = source.find(name)?;
+    let test_end = source[test_start + name.len()..]
+        .find("fn ")
+        .map_or(source.len(), |i| test_start + name.len() + i);
+
+    let placeholder = "assert_eq!(result, None);";
+    let offset = source[test_start..test_end].find(placeholder)?;
+    let at = test_start + offset;
+
+    let expected = if answer.parse::<i64>().is_ok() {
+        format!("Some({answer})")
+    } else {
+        format!("Some(String::from({answer:?}))")
+    };
+
+    Some(format!(
+        "{}assert_eq!(result, {expected});{}",
+        &source[..at],
+        &source[at + placeholder.len()..]
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn last_emphasized_code(html: &str) -> Option<String> {
+    ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| {
+            let start = html.rfind(open)?;
+            let content_start = start + open.len();
+            let end = html[content_start..].find('<')?;
+            Some((start, &html[content_start..content_start + end]))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| decode_entities(answer.trim()))
+        .filter(|answer| !answer.is_empty())
+}
+
+/// Removes all tags from a HTML fragment and decodes entities.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        code_blocks, guess_example_answers, guess_example_block, set_test_expectation, title,
+    };
+
+    const PUZZLE: &str = r#"<h2>--- Day 1: Historian Hysteria ---</h2>
+<p>The lists look like this:</p>
+<pre><code>a &lt; b</code></pre>
+<p>For example:</p>
+<pre><code>3   4
+4   <em>3</em>
+</code></pre>
+<p>In the example above, the total distance is <code><em>11</em></code>.</p>
+<p><em>What is the total distance between your lists?</em></p>
+
+
+<h2 id="part2">--- Part Two ---</h2>
+<p>So, for these example lists, the similarity score at the end is <em><code>31</code></em>.</p>
+<p>Once again consider your left and right lists. <em>What is their similarity score?</em></p>"#;
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(title(PUZZLE), Some("Historian Hysteria".into()));
+        assert_eq!(
+            title("<h2>--- Day 5: Print Queue &amp; Co ---</h2>"),
+            Some("Print Queue & Co".into())
+        );
+        assert_eq!(title("<p>no title</p>"), None);
+    }
+
+    #[test]
+    fn extracts_code_blocks() {
+        let blocks = code_blocks(PUZZLE);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text, "a < b");
+        assert_eq!(blocks[1].text, "3   4\n4   3\n");
+        assert!(blocks[1].preceding_text.contains("For example:"));
+        assert!(!blocks[1].preceding_text.contains("look like this"));
+    }
+
+    #[test]
+    fn guesses_example_block() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(guess_example_block(&blocks), Some(1));
+
+        let blocks = code_blocks("<pre><code>x</code></pre><pre><code>1\n2\n</code></pre>");
+        assert_eq!(guess_example_block(&blocks), Some(1));
+
+        assert_eq!(guess_example_block(&[]), None);
+    }
+
+    #[test]
+    fn guesses_example_answers() {
+        assert_eq!(
+            guess_example_answers(PUZZLE),
+            [Some("11".into()), Some("31".into())]
+        );
+
+        let part_one = &PUZZLE[..PUZZLE.find("<h2 id").unwrap()];
+        assert_eq!(guess_example_answers(part_one), [Some("11".into()), None]);
+    }
+
+    #[test]
+    fn sets_test_expectations() {
+        let source = "fn test_part_one() {\n    assert_eq!(result, None);\n}\n\nfn test_part_two() {\n    assert_eq!(result, None);\n}\n";
+
+        let source = set_test_expectation(source, 2, "6,1").unwrap();
+        assert!(source
+            .contains("test_part_two() {\n    assert_eq!(result, Some(String::from(\"6,1\")));"));
+
+        let source = set_test_expectation(&source, 1, "11").unwrap();
+        assert!(source.contains("test_part_one() {\n    assert_eq!(result, Some(11));"));
+
+        assert_eq!(set_test_expectation(&source, 1, "12"), None);
+    }
+}
@@ -0,0 +1,364 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Timing statistics collected while benching a part. All durations are in nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    pub samples: u64,
+    pub mean_nanos: f64,
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub p95_nanos: f64,
+    pub max_nanos: f64,
+    pub std_dev_nanos: f64,
+    /// Number of samples outside of the Tukey fences (1.5 times the interquartile range).
+    pub outliers: u64,
+}
+
+impl BenchStats {
+    /// Compute statistics for a set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Self {
+            samples: nanos.len() as u64,
+            mean_nanos: mean,
+            min_nanos: nanos[0],
+            median_nanos: percentile(&nanos, 50.0),
+            p95_nanos: percentile(&nanos, 95.0),
+            max_nanos: nanos[nanos.len() - 1],
+            std_dev_nanos: variance.sqrt(),
+            outliers: nanos.iter().filter(|&&x| x < lower || x > upper).count() as u64,
+        }
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+/// Part number of reports that time the shared parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// The result of running a single part of a solution.
+/// Solution binaries print one report per part as a JSON line when invoked with `--format json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error of a part that failed. Parts without answer and error are not implemented.
+    pub error: Option<String>,
+    /// Mean execution time in nanoseconds.
+    pub nanos: f64,
+    pub samples: u64,
+    pub stats: BenchStats,
+}
+
+impl PartReport {
+    /// Mean execution time as a [`Duration`].
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.nanos as u64)
+    }
+
+    /// Human-readable execution time, e.g. `74.1ns`.
+    pub fn duration_str(&self) -> String {
+        format!("{:.1?}", self.duration())
+    }
+
+    /// Whether the report times the shared parse step instead of a part.
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE_PART
+    }
+
+    /// Serialize the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self).stringify().unwrap_or_default()
+    }
+}
+
+impl Display for PartReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part = self.part;
+        let duration_str = if self.samples == 1 {
+            format!(" ({})", self.duration_str())
+        } else {
+            format!(" ({} @ {} samples)", self.duration_str(), self.samples)
+        };
+
+        if self.is_parse() {
+            return write!(f, "Parse:{duration_str}");
+        }
+
+        match &self.answer {
+            Some(answer) if answer.contains('\n') => {
+                write!(f, "Part {part}: ▼ {duration_str}\n{answer}")
+            }
+            Some(answer) => write!(
+                f,
+                "Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"
+            ),
+            None => match &self.error {
+                Some(error) => write!(f, "Part {part}: ✖ failed: {error}{duration_str}"),
+                None => write!(f, "Part {part}: ✖             "),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected report.{key} to be a number."))
+        };
+
+        let part = number("part")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected report.error to be a string.")?,
+            ),
+        };
+
+        let stats = json.get("stats").ok_or("Expected report to have stats.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            part: part as u8,
+            answer: answer.cloned(),
+            error,
+            nanos: number("nanos")?,
+            samples: number("samples")? as u64,
+            stats: BenchStats::try_from(stats)?,
+        })
+    }
+}
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u64,
+            mean_nanos: number("mean_nanos")?,
+            min_nanos: number("min_nanos")?,
+            median_nanos: number("median_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            max_nanos: number("max_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON line."))?;
+        PartReport::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, PartReport, PARSE_PART};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use std::time::Duration;
+
+    fn get_mock_report(answer: Option<&str>) -> PartReport {
+        PartReport {
+            part: 1,
+            answer: answer.map(String::from),
+            error: None,
+            nanos: 74.13,
+            samples: 100_000,
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(70),
+                Duration::from_nanos(74),
+                Duration::from_nanos(80),
+            ]),
+        }
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let report = get_mock_report(Some("42"));
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn round_trips_answers_with_patterns() {
+        let report = get_mock_report(Some("Part 2: @ ( ) ms\n\"#.#\""));
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed.answer, report.answer);
+    }
+
+    #[test]
+    fn round_trips_missing_answers() {
+        let report = get_mock_report(None);
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed.answer, None);
+    }
+
+    #[test]
+    fn round_trips_errors() {
+        let report = PartReport {
+            error: Some("no path found".into()),
+            ..get_mock_report(None)
+        };
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+        assert_eq!(
+            parsed.to_string(),
+            "Part 1: ✖ failed: no path found (74.0ns @ 100000 samples)"
+        );
+    }
+
+    #[test]
+    fn rejects_other_lines() {
+        assert!("Part 1: 42".parse::<PartReport>().is_err());
+        assert!(r#"{ "part": 1 }"#.parse::<PartReport>().is_err());
+    }
+
+    #[test]
+    fn formats_reports() {
+        let report = get_mock_report(Some("42"));
+        assert_eq!(
+            report.to_string(),
+            format!("Part 1: {ANSI_BOLD}42{ANSI_RESET} (74.0ns @ 100000 samples)")
+        );
+
+        let report = PartReport {
+            part: PARSE_PART,
+            ..get_mock_report(None)
+        };
+        assert_eq!(report.to_string(), "Parse: (74.0ns @ 100000 samples)");
+    }
+
+    #[test]
+    fn computes_stats() {
+        let samples: Vec<Duration> = (1..=20)
+            .map(|n| Duration::from_nanos(if n == 20 { 1000 } else { n }))
+            .collect();
+        let stats = BenchStats::from_samples(&samples);
+
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.mean_nanos, 59.5);
+        assert_eq!(stats.min_nanos, 1.0);
+        assert_eq!(stats.max_nanos, 1000.0);
+        assert_eq!(stats.median_nanos, 10.5);
+        assert!((stats.p95_nanos - 68.05).abs() < 1e-6);
+        assert_eq!(stats.outliers, 1);
+        assert!(stats.std_dev_nanos > 200.0);
+    }
+
+    #[test]
+    fn computes_stats_for_single_sample() {
+        let stats = BenchStats::from_samples(&[Duration::from_nanos(5)]);
+        assert_eq!(stats.median_nanos, 5.0);
+        assert_eq!(stats.p95_nanos, 5.0);
+        assert_eq!(stats.std_dev_nanos, 0.0);
+        assert_eq!(stats.outliers, 0);
+    }
+}
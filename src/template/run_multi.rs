@@ -1,38 +1,47 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    all_days, bin_name,
+    report::PartReport,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    year: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+    bench_budget: Option<u64>,
+    jobs: usize,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    for_each_day(
+        days_to_run,
+        year,
+        is_release,
+        is_timed,
+        bench_budget,
+        jobs,
+        |day, reports| {
+            if reports.is_empty() {
+                return;
             }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+            let val = child_commands::parse_exec_time(reports, day);
+            timings.push(val);
+        },
+    );
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,10 +55,125 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run the solutions for a set of days in order, printing their output.
+/// `on_output` is called with the part reports of every day. The reports are empty if the solution
+/// failed to build, panicked or solved no part.
+///
+/// With more than one job, all binaries are built up front and days run concurrently.
+/// Output is buffered and printed per day, in order. Timed runs are never run concurrently.
+pub fn for_each_day(
+    days_to_run: &HashSet<Day>,
+    year: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+    bench_budget: Option<u64>,
+    jobs: usize,
+    mut on_output: impl FnMut(Day, &[PartReport]),
+) {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    if jobs <= 1 {
+        for (i, day) in days.into_iter().enumerate() {
+            print_day_header(day, i > 0);
+            let output = child_commands::run_solution(
+                year,
+                day,
+                is_timed,
+                is_release,
+                bench_budget,
+                child_commands::Mode::Streamed,
+            )
+            .unwrap();
+            print_day_result(day, &output, &mut on_output);
+        }
+        return;
+    }
+
+    if let Err(e) = child_commands::build_all(is_release) {
+        eprintln!("Failed to build solutions: {e:?}");
+        process::exit(1);
+    }
+
+    // timed runs are serialized so that benchmarks do not compete for the CPU.
+    let workers = if is_timed { 1 } else { jobs.min(days.len()) };
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (days, next) = (&days, &next);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+
+                let output = child_commands::run_solution(
+                    year,
+                    day,
+                    is_timed,
+                    is_release,
+                    bench_budget,
+                    child_commands::Mode::Buffered,
+                )
+                .unwrap();
+
+                if tx.send((i, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        // days can finish in any order, hold back output until all previous days were printed.
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, output) in rx {
+            pending.insert(i, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                let day = days[next_to_print];
+                print_day_header(day, next_to_print > 0);
+                output.print();
+                print_day_result(day, &output, &mut on_output);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_day_result(
+    day: Day,
+    output: &child_commands::SolutionOutput,
+    on_output: &mut impl FnMut(Day, &[PartReport]),
+) {
+    if output.reports.is_empty() {
+        println!("Not solved.");
+    }
+
+    on_output(day, &output.reports);
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
     IO(io::Error),
 }
 
@@ -60,198 +184,287 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the answer and timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{bin_name, report::PartReport, Day, Year};
     use std::{
+        env,
+        env::consts::EXE_SUFFIX,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// How a solution binary is invoked.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Mode {
+        /// Build and run the binary with `cargo run`, forwarding its output as it runs.
+        Streamed,
+        /// Run a binary previously built with [`build_all`], buffering its output.
+        Buffered,
+    }
+
+    /// The output of a solution binary.
+    #[derive(Debug, Default)]
+    pub struct SolutionOutput {
+        pub reports: Vec<PartReport>,
+        /// Lines printed to stdout, with part reports rendered for humans.
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+    }
+
+    impl SolutionOutput {
+        /// Print buffered output.
+        pub fn print(&self) {
+            self.stdout.iter().for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
         }
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Build all solution binaries.
+    pub fn build_all(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
 
         if is_release {
             args.push("--release");
         }
 
+        let status = Command::new("cargo").args(&args).status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
+        }
+    }
+
+    /// Path of a binary built by [`build_all`].
+    fn get_path_for_built_bin(year: Option<Year>, day: Day, is_release: bool) -> PathBuf {
+        let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        let profile = if is_release { "release" } else { "debug" };
+
+        Path::new(&target_dir)
+            .join(profile)
+            .join(format!("{}{EXE_SUFFIX}", bin_name(year, day)))
+    }
+
+    /// Run the solution bin for a given day.
+    /// `bench_budget` overrides the time budget (in milliseconds) for benching each part.
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        bench_budget: Option<u64>,
+        mode: Mode,
+    ) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok(SolutionOutput::default());
+        }
+
+        let budget = bench_budget.map(|b| b.to_string());
+
+        // request machine-readable part reports from the child.
+        let mut args = vec!["--format", "json"];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
+
+            if let Some(budget) = &budget {
+                args.extend(["--budget", budget]);
+            }
         }
 
+        let mut cmd = match mode {
+            Mode::Streamed => {
+                let mut cmd = Command::new("cargo");
+                cmd.args(["run", "--quiet", "--bin", &bin_name(year, day)]);
+                if is_release {
+                    cmd.arg("--release");
+                }
+                cmd.arg("--").args(&args);
+                cmd
+            }
+            Mode::Buffered => {
+                let mut cmd = Command::new(get_path_for_built_bin(year, day, is_release));
+                cmd.args(&args);
+                cmd
+            }
+        };
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward or buffer output while collecting part reports.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let is_streamed = mode == Mode::Streamed;
+        let mut output = SolutionOutput::default();
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut lines = vec![];
+            for line in stderr.lines() {
+                let line = line.unwrap();
+                if is_streamed {
+                    eprintln!("{line}");
+                } else {
+                    lines.push(line);
+                }
+            }
+            lines
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            // anything that is not a report was printed by the solution itself.
+            let line = match line.parse::<PartReport>() {
+                Ok(report) => {
+                    let rendered = report.to_string();
+                    output.reports.push(report);
+                    rendered
+                }
+                Err(_) => line,
+            };
+
+            if is_streamed {
+                println!("{line}");
+            } else {
+                output.stdout.push(line);
+            }
         }
 
-        thread.join().unwrap();
+        output.stderr = thread.join().unwrap();
         cmd.wait()?;
 
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_exec_time(reports: &[PartReport], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
             total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|report| report.answer.is_some() && !report.is_parse())
+            .for_each(|report| {
+                let timing_str = Some(report.duration_str());
+                let stats = Some(report.stats);
+
+                match report.part {
+                    1 => (timings.part_1, timings.part_1_stats) = (timing_str, stats),
+                    2 => (timings.part_2, timings.part_2_stats) = (timing_str, stats),
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += report.nanos;
             });
 
+        // the shared parse step counts towards the total of solved days.
+        if timings.part_1.is_some() || timings.part_2.is_some() {
+            timings.total_nanos += reports
+                .iter()
+                .filter(|report| report.is_parse())
+                .map(|report| report.nanos)
+                .sum::<f64>();
+        }
+
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Collect the answers of a solution. Parts without a result are returned as `None`.
+    pub fn parse_answers(reports: &[PartReport]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+        for report in reports {
+            let index = usize::from(report.part).checked_sub(1);
+            if let Some(answer) = index.and_then(|i| answers.get_mut(i)) {
+                answer.clone_from(&report.answer);
+            }
+        }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        answers
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
+        use super::{parse_answers, parse_exec_time};
         use crate::day;
+        use crate::template::report::{BenchStats, PartReport, PARSE_PART};
+
+        fn report(part: u8, answer: Option<&str>, nanos: f64) -> PartReport {
+            PartReport {
+                part,
+                answer: answer.map(String::from),
+                error: None,
+                nanos,
+                samples: 10,
+                stats: BenchStats::default(),
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74.0),
+                    report(2, Some("10"), 74_130_000.0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats, Some(BenchStats::default()));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn adds_parse_time_to_total() {
+            let reports = [
+                report(PARSE_PART, None, 50.0),
+                report(1, Some("0"), 100.0),
+                report(2, Some("1"), 200.0),
+            ];
+            let res = parse_exec_time(&reports, day!(1));
+            assert_eq!(res.total_nanos, 350_f64);
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
+            assert_eq!(
+                parse_answers(&reports),
+                [Some("0".into()), Some("1".into())]
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+
+            let res = parse_exec_time(&[report(PARSE_PART, None, 50.0)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            let res = parse_exec_time(&[report(1, None, 10.0), report(2, None, 10.0)], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[report(2, Some("#..#\n####"), 1.0), report(1, None, 1.0)]);
+            assert_eq!(res, [None, Some("#..#\n####".into())]);
+        }
     }
 }
@@ -2,34 +2,206 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{AnswerCheck, Answers, Verdict};
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionOutcome};
+use crate::template::examples::{Example, Manifest};
+use crate::template::report::{BenchStats, PartReport, PARSE_PART};
+use crate::template::{
+    Context, Day, IntoOutcome, Outcome, Parse, Part, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Run the shared parse step of a solution against the real input and report its timing.
+pub fn run_parse<M, P>(func: impl Parse<M, P>, input: &str) -> P {
+    let is_json = is_json_output();
+    let ctx = Context::real();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (parsed, timers) = run_timed(
+        |input| func.call(input, &ctx),
+        input,
+        |parsed| {
+            if !is_json {
+                print!("Parse:");
+            }
+            parsed
+        },
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let report = build_report(&Outcome::NotImplemented, PARSE_PART, &timers);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if is_json {
+        println!("{}", report.to_json_line());
+    } else {
+        print!("\r");
+        println!("{report}");
     }
+
+    parsed
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+/// Run a solution part against the real input.
+pub fn run_part<M, I: ?Sized, R: IntoOutcome>(
+    func: impl Part<M, I, R>,
+    input: &I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) {
+    let is_json = is_json_output();
+    let ctx = Context::real();
+
+    let (outcome, timers) = run_timed(
+        |input| func.call(input, &ctx),
+        input,
+        |result| {
+            let outcome = result.into_outcome();
+            if !is_json {
+                print_intermediate_result(&outcome, part);
+            }
+            outcome
+        },
+    );
+
+    let report = build_report(&outcome, part, &timers);
+
+    if is_json {
+        println!("{}", report.to_json_line());
+        return;
+    }
+
+    print!("\r");
+    println!("{report}");
+
+    if let Some(answer) = outcome.answer() {
+        match submit_result(answer, year, day, part) {
+            Some(Ok(outcome)) => println!("{outcome}"),
+            Some(Err(e)) => {
+                eprintln!("Failed to submit result: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Whether the solution was invoked with `--examples`.
+pub fn is_examples_mode() -> bool {
+    env::args().any(|x| x == "--examples")
+}
+
+/// Read the examples of a day together with their inputs. Exits if the manifest or an input can not be read.
+pub fn read_examples(year: Option<Year>, day: Day) -> Vec<(Example, String)> {
+    let manifest = Manifest::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read example manifest: {e}");
+        process::exit(1);
+    });
+
+    manifest
+        .examples
+        .into_iter()
+        .map(|example| match example.read_input(year) {
+            Ok(input) => (example, input),
+            Err(e) => {
+                eprintln!("Failed to read example \"{}\": {e}", example.name);
+                process::exit(1);
+            }
+        })
+        .collect()
+}
+
+/// Tally of checked example answers.
+#[derive(Debug, Default)]
+pub struct ExampleResults {
+    passed: usize,
+    failed: usize,
+    unchecked: usize,
+}
+
+impl ExampleResults {
+    /// Print a summary and exit with a non-zero status if any example failed.
+    pub fn finish(&self) {
+        println!(
+            "\n{ANSI_BOLD}Examples:{ANSI_RESET} {} passed, {} failed, {} unchecked",
+            self.passed, self.failed, self.unchecked
+        );
+
+        if self.failed > 0 {
+            process::exit(1);
+        }
+    }
+}
+
+pub fn print_example_header(example: &Example) {
+    println!("{ANSI_BOLD}Example \"{}\"{ANSI_RESET}", example.name);
+}
+
+/// Run the shared parse step of a solution against an example.
+pub fn parse_example<M, P>(func: impl Parse<M, P>, input: &str, example: &Example) -> P {
+    func.call(input, &example.context())
+}
+
+/// Run a solution part against an example and compare the result with the expected answer.
+pub fn run_example_part<M, I: ?Sized, R: IntoOutcome>(
+    func: impl Part<M, I, R>,
+    input: &I,
+    example: &Example,
+    part: u8,
+    results: &mut ExampleResults,
+) {
+    let outcome = func.call(input, &example.context()).into_outcome();
+    let result = outcome.answer().map(ToString::to_string);
+    let result_str = match &outcome {
+        Outcome::Solved(answer) => answer.to_string(),
+        Outcome::NotImplemented => "✖".into(),
+        Outcome::Failed(e) => format!("✖ failed: {e}"),
+    };
+
+    match (example.expected(part), result.as_deref()) {
+        (None, _) => {
+            results.unchecked += 1;
+            println!("Part {part}: {result_str} {ANSI_ITALIC}(no expected answer){ANSI_RESET}");
+        }
+        (Some(expected), Some(result)) if expected == result => {
+            results.passed += 1;
+            println!("Part {part}: {ANSI_BOLD}{result}{ANSI_RESET} ✔");
+        }
+        (Some(expected), _) => {
+            results.failed += 1;
+            println!("Part {part}: {result_str} ✘ expected {ANSI_BOLD}{expected}{ANSI_RESET}");
+        }
+    }
+}
+
+/// Whether the solution was invoked with `--format json`, i.e. by a parent process.
+fn is_json_output() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .any(|w| w[0] == "--format" && w[1] == "json")
+}
+
+/// Time budget for benching a part, configurable with `--budget <millis>`.
+fn bench_budget() -> Duration {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .find(|w| w[0] == "--budget")
+        .and_then(|w| w[1].parse().ok())
+        .map_or(DEFAULT_BENCH_BUDGET, Duration::from_millis)
+}
+
+const DEFAULT_BENCH_BUDGET: Duration = Duration::from_secs(1);
+
+/// Run a solution part. The behavior differs depending on whether the `--time` flag is passed:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched (approx. the time budget of execution time or 10 samples, whatever take longer.)
+///
+/// `hook` receives the result of the first run before benching and converts it to the returned value.
+fn run_timed<I: Clone, T, U>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl FnOnce(T) -> U,
+) -> (U, Vec<Duration>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,25 +213,32 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let timers = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        vec![base_time]
     };
 
-    (result, run.0, run.1)
+    (result, timers)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Vec<Duration> {
+    if !is_json_output() {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (bench_budget().as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before measuring.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,11 +250,21 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers
+}
+
+fn build_report(outcome: &Outcome, part: u8, timers: &[Duration]) -> PartReport {
+    #[allow(clippy::cast_precision_loss)]
+    let nanos = average_duration(timers) as f64;
+
+    PartReport {
+        part,
+        answer: outcome.answer().map(ToString::to_string),
+        error: outcome.error().map(String::from),
+        nanos,
+        samples: timers.len() as u64,
+        stats: BenchStats::from_samples(timers),
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -86,57 +275,26 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
-}
-
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
-
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+/// Print the result before benching. It is overwritten by the final report once benching completes.
+fn print_intermediate_result(outcome: &Outcome, part: u8) {
+    match outcome {
+        Outcome::Solved(answer) if answer.is_multiline() => print!("Part {part}: ▼"),
+        Outcome::Solved(answer) => print!("Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}"),
+        Outcome::NotImplemented => print!("Part {part}: ✖"),
+        Outcome::Failed(e) => print!("Part {part}: ✖ failed: {e}"),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the answer ledger does not already rule the answer out.
 fn submit_result<T: Display>(
     result: T,
+    year: Option<Year>,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +317,66 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+    let mut answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read the answer ledger, not submitting: {e}");
+            return None;
+        }
+    };
+
+    if !check_answer(&answers, day, part, &answer) {
+        return None;
+    }
+
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => return Some(Err(e)),
+    };
+
+    println!("Submitting result...");
+    let outcome = client.submit(day, part, &answer);
+
+    if let Ok(outcome) = &outcome {
+        if answers.record(day, part, &answer, outcome) && answers.store_file(year).is_err() {
+            eprintln!("Failed to record answer in the answer ledger.");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(outcome)
+}
+
+/// Consult the answer ledger before submitting. Returns `false` if the answer should not be submitted.
+fn check_answer(answers: &Answers, day: Day, part: u8, answer: &str) -> bool {
+    match answers.check(day, part, answer) {
+        AnswerCheck::Unknown => true,
+        AnswerCheck::Solved(correct) if correct == answer => {
+            println!("Part {part} is already solved with this answer, not submitting.");
+            false
+        }
+        AnswerCheck::Solved(correct) => {
+            eprintln!(
+                "Part {part} is already solved with answer {correct}, but the solution returned {answer}. Not submitting."
+            );
+            false
+        }
+        AnswerCheck::KnownWrong(_) => {
+            eprintln!(
+                "{answer} was already submitted for part {part} and is wrong, not submitting."
+            );
+            false
+        }
+        AnswerCheck::ContradictsBound { bound, verdict } => {
+            let relation = if verdict == Verdict::TooHigh {
+                "too high"
+            } else {
+                "too low"
+            };
+            eprintln!(
+                "Warning: {answer} contradicts a previous answer for part {part}: {bound} was {relation}."
+            );
+            true
+        }
+    }
 }
@@ -0,0 +1,122 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Watches files for changes by polling their modification times.
+///
+/// Polling works the same on every platform and does not depend on notifier services.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    files: Vec<PathBuf>,
+    /// Directories of which files starting with a prefix are watched.
+    dirs: Vec<(PathBuf, String)>,
+    state: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    /// Watch a single file. The file does not need to exist yet.
+    #[must_use]
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Watch all files of a directory whose name starts with `prefix`, including files created later.
+    #[must_use]
+    pub fn files_in(mut self, dir: impl Into<PathBuf>, prefix: &str) -> Self {
+        self.dirs.push((dir.into(), prefix.into()));
+        self
+    }
+
+    /// Number of watched files that currently exist.
+    pub fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Checks the watched files once. Returns `true` if a file was changed, created or removed since the last check.
+    pub fn poll(&mut self) -> bool {
+        let state = self.scan();
+        let changed = state != self.state;
+        self.state = state;
+        changed
+    }
+
+    /// Blocks until a watched file changes, checking every `interval`.
+    pub fn wait_for_change(&mut self, interval: Duration) {
+        while !self.poll() {
+            thread::sleep(interval);
+        }
+    }
+
+    fn scan(&self) -> BTreeMap<PathBuf, SystemTime> {
+        let dir_files = self.dirs.iter().flat_map(|(dir, prefix)| {
+            fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .filter(move |entry| entry.file_name().to_string_lossy().starts_with(prefix))
+                .map(|entry| entry.path())
+        });
+
+        self.files
+            .iter()
+            .cloned()
+            .chain(dir_files)
+            .filter_map(|path| modified(&path).map(|time| (path, time)))
+            .collect()
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process, time::SystemTime};
+
+    use super::Watcher;
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("07.txt");
+        fs::write(&file, "a").unwrap();
+
+        let mut watcher = Watcher::default()
+            .file(dir.join("missing.txt"))
+            .files_in(&dir, "07");
+
+        assert!(watcher.poll());
+        assert_eq!(watcher.len(), 1);
+        assert!(!watcher.poll());
+
+        // bump the modification time explicitly, file systems may have a coarse resolution.
+        let later = SystemTime::now() + std::time::Duration::from_secs(10);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(watcher.poll());
+
+        fs::write(dir.join("07-small.txt"), "b").unwrap();
+        fs::write(dir.join("08.txt"), "c").unwrap();
+        assert!(watcher.poll());
+        assert_eq!(watcher.len(), 2);
+
+        fs::remove_file(&file).unwrap();
+        assert!(watcher.poll());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// Size of a character cell in pixels.
+const CELL_SIZE: usize = 4;
+
+/// Colors of a GIF as `[r, g, b, ...]`. Blank cells use the first, `#` the second color.
+const PALETTE: [u8; 48] = [
+    0x0f, 0x0f, 0x23, // background
+    0xcc, 0xcc, 0xcc, // walls
+    0xff, 0xff, 0x66, 0x00, 0x99, 0x00, 0xe6, 0x39, 0x46, 0x45, 0x7b, 0x9d, //
+    0xf4, 0xa2, 0x61, 0x2a, 0x9d, 0x8f, 0xe9, 0xc4, 0x6a, 0x9b, 0x5d, 0xe5, //
+    0xf1, 0x5b, 0xb5, 0x00, 0xbb, 0xf9, 0x00, 0xf5, 0xd4, 0xff, 0xff, 0xff, //
+    0x8d, 0x99, 0xae, 0xef, 0x23, 0x3c,
+];
+
+/// Writes frames to a `.gif` file, or to a text file for any other extension.
+pub fn export(frames: &[String], path: &Path, fps: u32) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    if path.extension().is_some_and(|ext| ext == "gif") {
+        write_gif(&mut file, frames, fps)?;
+    } else {
+        write_text(&mut file, frames)?;
+    }
+
+    file.flush()
+}
+
+/// Writes all frames, each preceded by a header line.
+fn write_text(out: &mut impl Write, frames: &[String]) -> io::Result<()> {
+    for (i, frame) in frames.iter().enumerate() {
+        writeln!(out, "Frame {}/{}", i + 1, frames.len())?;
+        writeln!(out, "{}\n", strip_ansi(frame))?;
+    }
+    Ok(())
+}
+
+/// Writes an animated GIF. Every character becomes a square of pixels, colored by [`color_index`].
+fn write_gif(out: &mut impl Write, frames: &[String], fps: u32) -> io::Result<()> {
+    let frames: Vec<Vec<Vec<char>>> = frames
+        .iter()
+        .map(|frame| {
+            strip_ansi(frame)
+                .lines()
+                .map(|line| line.chars().collect())
+                .collect()
+        })
+        .collect();
+
+    let columns = frames.iter().flatten().map(Vec::len).max().unwrap_or(0);
+    let rows = frames.iter().map(Vec::len).max().unwrap_or(0);
+
+    let (width, height) = (columns * CELL_SIZE, rows * CELL_SIZE);
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::other("frames are too large for a GIF"));
+    };
+
+    let mut encoder =
+        gif::Encoder::new(&mut *out, gif_width, gif_height, &PALETTE).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    // GIF delays are in hundredths of a second, most viewers ignore delays below 2.
+    let delay = u16::try_from(100 / fps.max(1)).unwrap_or(u16::MAX).max(2);
+
+    for frame in frames {
+        let mut pixels = vec![0; width * height];
+
+        for (y, line) in frame.iter().enumerate() {
+            for (x, &c) in line.iter().enumerate() {
+                let color = color_index(c);
+                for dy in 0..CELL_SIZE {
+                    let start = (y * CELL_SIZE + dy) * width + x * CELL_SIZE;
+                    pixels[start..start + CELL_SIZE].fill(color);
+                }
+            }
+        }
+
+        let mut frame = gif::Frame::from_indexed_pixels(gif_width, gif_height, pixels, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+/// Color of a character in the GIF palette. Blank cells (` ` and `.`) are dark, `#` is light gray,
+/// every other character is assigned one of the remaining colors.
+fn color_index(c: char) -> u8 {
+    match c {
+        ' ' | '.' => 0,
+        '#' => 1,
+        #[allow(clippy::cast_possible_truncation)]
+        c => 2 + (u32::from(c) % (PALETTE.len() as u32 / 3 - 2)) as u8,
+    }
+}
+
+/// Removes ANSI escape sequences, e.g. colors.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the `[`, parameters and the final byte of a control sequence.
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{color_index, strip_ansi, write_gif, write_text};
+
+    #[test]
+    fn strips_ansi() {
+        assert_eq!(strip_ansi("\x1b[1m#\x1b[0m.\x1b[38;5;1mO"), "#.O");
+    }
+
+    #[test]
+    fn writes_text() {
+        let mut out = vec![];
+        write_text(&mut out, &["#.".into(), "\x1b[1m.#\x1b[0m".into()]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Frame 1/2\n#.\n\nFrame 2/2\n.#\n\n"
+        );
+    }
+
+    #[test]
+    fn writes_gifs() {
+        let mut out = vec![];
+        write_gif(&mut out, &["#.\n.O".into(), "..\n#".into()], 10).unwrap();
+
+        assert_eq!(&out[..6], b"GIF89a");
+        // canvas size in little-endian, 2x2 cells of 4 pixels.
+        assert_eq!(&out[6..10], &[8, 0, 8, 0]);
+        assert_eq!(out.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn assigns_colors() {
+        assert_eq!(color_index('.'), 0);
+        assert_eq!(color_index(' '), 0);
+        assert_eq!(color_index('#'), 1);
+        assert!((2..16).contains(&color_index('@')));
+    }
+}
@@ -0,0 +1,171 @@
+/// Step-by-step visualizations of simulations.
+///
+/// Solutions feed frames into a [`Visualizer`], which plays them as a terminal animation or exports them to a file
+/// once it is dropped. Recording is only compiled in with the `visualize` feature and only enabled when the solution
+/// is run with `--visualize` or `--export <path>`. Without them, all methods are no-ops and frames are never rendered,
+/// so benchmarks are unaffected.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// # use advent_of_code::visualize::Visualizer;
+/// let mut grid: Grid<char> = "..\n..".parse().unwrap();
+/// let mut vis = Visualizer::new();
+///
+/// for point in grid.points().collect::<Vec<_>>() {
+///     grid[point] = '#';
+///     vis.grid(&grid);
+/// }
+/// ```
+use std::fmt::Display;
+
+use crate::grid::Grid;
+
+#[cfg(feature = "visualize")]
+mod export;
+#[cfg(feature = "visualize")]
+mod player;
+
+/// Frames per second used if `--fps` is not passed.
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Maximum number of recorded frames. Later frames are dropped, so long simulations can not exhaust memory.
+pub const MAX_FRAMES: usize = 10_000;
+
+/// Records frames of a simulation and plays or exports them when dropped.
+#[derive(Debug, Default)]
+pub struct Visualizer {
+    #[cfg(feature = "visualize")]
+    recording: Option<Recording>,
+}
+
+impl Visualizer {
+    /// Creates a visualizer configured by the command-line arguments of the solution:
+    ///  - `--visualize`: play the frames as a terminal animation.
+    ///  - `--export <path>`: write the frames to a `.gif` file, or a text file for any other extension.
+    ///  - `--fps <n>`: initial playback speed, defaults to [`DEFAULT_FPS`].
+    ///
+    /// Visualizations are disabled for benchmarks (`--time`) and without the `visualize` feature.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "visualize")]
+            recording: Recording::from_args(),
+        }
+    }
+
+    /// Whether frames are recorded. Use this to skip work that is only needed for visualizing.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        #[cfg(feature = "visualize")]
+        return self.recording.is_some();
+
+        #[cfg(not(feature = "visualize"))]
+        false
+    }
+
+    /// Records a frame. `render` is only called if the visualizer is enabled.
+    ///
+    /// After [`MAX_FRAMES`] frames, recording stops with a warning.
+    #[inline]
+    #[allow(unused_variables)]
+    pub fn frame(&mut self, render: impl FnOnce() -> String) {
+        #[cfg(feature = "visualize")]
+        if let Some(recording) = &mut self.recording {
+            recording.push(render);
+        }
+    }
+
+    /// Records a grid of [`Display`] cells as a frame.
+    #[inline]
+    pub fn grid<T: Display>(&mut self, grid: &Grid<T>) {
+        self.frame(|| grid.to_string());
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl Drop for Visualizer {
+    fn drop(&mut self) {
+        let Some(recording) = self.recording.take() else {
+            return;
+        };
+
+        if let Err(e) = recording.finish() {
+            eprintln!("Failed to visualize: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "visualize")]
+#[derive(Debug)]
+enum Output {
+    Play,
+    Export(std::path::PathBuf),
+}
+
+#[cfg(feature = "visualize")]
+#[derive(Debug)]
+struct Recording {
+    output: Output,
+    fps: u32,
+    frames: Vec<String>,
+    /// Whether frames were dropped after reaching [`MAX_FRAMES`].
+    truncated: bool,
+}
+
+#[cfg(feature = "visualize")]
+impl Recording {
+    fn from_args() -> Option<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        let value_of = |key: &str| args.windows(2).find(|w| w[0] == key).map(|w| w[1].clone());
+
+        if args.iter().any(|x| x == "--time") {
+            return None;
+        }
+
+        let output = match value_of("--export") {
+            Some(path) => Output::Export(path.into()),
+            None if args.iter().any(|x| x == "--visualize") => Output::Play,
+            None => return None,
+        };
+
+        Some(Self {
+            output,
+            fps: value_of("--fps")
+                .and_then(|fps| fps.parse().ok())
+                .filter(|&fps| fps > 0)
+                .unwrap_or(DEFAULT_FPS),
+            frames: vec![],
+            truncated: false,
+        })
+    }
+
+    fn push(&mut self, render: impl FnOnce() -> String) {
+        if self.frames.len() < MAX_FRAMES {
+            self.frames.push(render());
+        } else if !self.truncated {
+            self.truncated = true;
+            eprintln!("Recorded {MAX_FRAMES} frames, dropping the remaining frames.");
+        }
+    }
+
+    fn finish(self) -> std::io::Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        match &self.output {
+            Output::Play => player::play(&self.frames, self.fps),
+            Output::Export(path) => {
+                export::export(&self.frames, path, self.fps)?;
+                eprintln!(
+                    "Exported {} frames to \"{}\".",
+                    self.frames.len(),
+                    path.display()
+                );
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    io::{self, stdin, stdout, IsTerminal, Read, Write},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_HOME_CLEAR: &str = "\x1b[H\x1b[2J";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+
+const MAX_FPS: u32 = 960;
+
+/// Keyboard controls of the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Key {
+    TogglePlay,
+    StepForward,
+    StepBack,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Key {
+    /// Parses a chunk of input, arrow keys arrive as escape sequences.
+    fn parse(input: &[u8]) -> Option<Self> {
+        match input {
+            [b' ', ..] => Some(Key::TogglePlay),
+            [b'n' | b'l', ..] | [0x1b, b'[', b'C', ..] => Some(Key::StepForward),
+            [b'p' | b'h', ..] | [0x1b, b'[', b'D', ..] => Some(Key::StepBack),
+            [b'+' | b'=', ..] => Some(Key::Faster),
+            [b'-' | b'_', ..] => Some(Key::Slower),
+            [b'q', ..] | [0x1b] => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Plays frames as a terminal animation.
+///
+/// If stdin is a terminal, the animation can be controlled with the keyboard. Otherwise, all frames are played once.
+pub fn play(frames: &[String], fps: u32) -> io::Result<()> {
+    let raw_mode = RawMode::enable();
+    let keys = raw_mode.as_ref().map(|_| read_keys());
+
+    let mut stdout = stdout().lock();
+    write!(stdout, "{ANSI_HIDE_CURSOR}")?;
+
+    let mut index = 0;
+    let mut fps = fps;
+    let mut is_playing = true;
+
+    loop {
+        let is_last = index + 1 == frames.len();
+        draw(
+            &mut stdout,
+            frames,
+            index,
+            fps,
+            is_playing && !is_last,
+            keys.is_some(),
+        )?;
+
+        let delay = Duration::from_secs_f64(1.0 / f64::from(fps));
+
+        let key = match (&keys, is_playing && !is_last) {
+            (Some(keys), true) => keys.recv_timeout(delay).ok(),
+            (Some(keys), false) => match keys.recv() {
+                Ok(key) => Some(key),
+                Err(_) => break,
+            },
+            (None, true) => {
+                thread::sleep(delay);
+                None
+            }
+            (None, false) => break,
+        };
+
+        match key {
+            None => index += 1,
+            Some(Key::TogglePlay) if is_last => (index, is_playing) = (0, true),
+            Some(Key::TogglePlay) => is_playing = !is_playing,
+            Some(Key::StepForward) => {
+                is_playing = false;
+                index = (index + 1).min(frames.len() - 1);
+            }
+            Some(Key::StepBack) => {
+                is_playing = false;
+                index = index.saturating_sub(1);
+            }
+            Some(Key::Faster) => fps = (fps * 2).min(MAX_FPS),
+            Some(Key::Slower) => fps = (fps / 2).max(1),
+            Some(Key::Quit) => break,
+        }
+    }
+
+    write!(stdout, "{ANSI_SHOW_CURSOR}")?;
+    stdout.flush()?;
+
+    drop(raw_mode);
+    Ok(())
+}
+
+fn draw(
+    out: &mut impl Write,
+    frames: &[String],
+    index: usize,
+    fps: u32,
+    is_playing: bool,
+    has_controls: bool,
+) -> io::Result<()> {
+    let state = if is_playing { "▶" } else { "⏸" };
+
+    write!(out, "{ANSI_HOME_CLEAR}{}\n\n", frames[index])?;
+    write!(
+        out,
+        "{ANSI_BOLD}{state} Frame {}/{}{ANSI_RESET} @ {fps} fps",
+        index + 1,
+        frames.len()
+    )?;
+    if has_controls {
+        write!(
+            out,
+            "  {ANSI_ITALIC}[space] play/pause  [←/→] step  [+/-] speed  [q] quit{ANSI_RESET}"
+        )?;
+    }
+    writeln!(out)?;
+    out.flush()
+}
+
+/// Reads keys from stdin on a background thread.
+fn read_keys() -> Receiver<Key> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut stdin = stdin();
+        let mut buf = [0; 8];
+
+        while let Ok(n @ 1..) = stdin.read(&mut buf) {
+            if let Some(key) = Key::parse(&buf[..n]) {
+                if tx.send(key).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+/// Disables line buffering and echo of the terminal with `stty`, restoring its settings when dropped.
+/// Returns [`None`] if stdin is not a terminal or `stty` is not available.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<Self> {
+        if !stdin().is_terminal() {
+            return None;
+        }
+
+        let saved = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+
+        let status = Command::new("stty")
+            .args(["-icanon", "-echo", "min", "1"])
+            .stdin(Stdio::inherit())
+            .status()
+            .ok()?;
+
+        status.success().then(|| RawMode {
+            saved: String::from_utf8_lossy(&saved.stdout).trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = Command::new("stty")
+            .arg(&self.saved)
+            .stdin(Stdio::inherit())
+            .status();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{draw, Key};
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(Key::parse(b" "), Some(Key::TogglePlay));
+        assert_eq!(Key::parse(b"\x1b[C"), Some(Key::StepForward));
+        assert_eq!(Key::parse(b"\x1b[D"), Some(Key::StepBack));
+        assert_eq!(Key::parse(b"+"), Some(Key::Faster));
+        assert_eq!(Key::parse(b"-"), Some(Key::Slower));
+        assert_eq!(Key::parse(b"q"), Some(Key::Quit));
+        assert_eq!(Key::parse(b"\x1b"), Some(Key::Quit));
+        assert_eq!(Key::parse(b"\x1b[A"), None);
+        assert_eq!(Key::parse(b"\x1b[B"), None);
+        assert_eq!(Key::parse(b"x"), None);
+    }
+
+    #[test]
+    fn draws_frames() {
+        let mut out = vec![];
+        draw(&mut out, &["#.".into(), ".#".into()], 1, 10, false, false).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(".#\n\n"));
+        assert!(out.contains("Frame 2/2"));
+        assert!(!out.contains("[q] quit"));
+    }
+}
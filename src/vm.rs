@@ -0,0 +1,572 @@
+/// A small framework for the register machines that puzzles keep coming back to.
+///
+/// An instruction set is declared by implementing [`InstructionSet`] for an instruction type: its register names,
+/// how it is decoded from bytecode and what it does to the registers. [`Machine`] then runs programs of these
+/// instructions step by step or until they halt, with breakpoints, tracing and a disassembler.
+///
+/// Puzzles that ask for a register value producing a given output can often be solved backwards, one digit of the
+/// register at a time, see [`search_digits`].
+///
+/// ```
+/// # use std::fmt::{self, Display};
+/// # use advent_of_code::vm::{Effect, InstructionSet, Machine};
+/// #[derive(Clone, Debug)]
+/// enum Op { Inc(usize), Out(usize), Jnz(usize, isize) }
+///
+/// impl InstructionSet for Op {
+///     const REGISTERS: &'static [&'static str] = &["a", "b"];
+///
+///     fn execute(&self, registers: &mut [i64]) -> Result<Effect, String> {
+///         Ok(match *self {
+///             Op::Inc(r) => { registers[r] += 1; Effect::Next }
+///             Op::Out(r) => Effect::Output(registers[r]),
+///             Op::Jnz(r, offset) if registers[r] != 0 => Effect::Offset(offset),
+///             Op::Jnz(..) => Effect::Next,
+///         })
+///     }
+/// }
+/// # impl Display for Op {
+/// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{self:?}") }
+/// # }
+///
+/// let mut machine = Machine::new(vec![Op::Inc(0), Op::Out(0)], &[0, 0]);
+/// machine.run().unwrap();
+/// assert_eq!(machine.output(), &[1]);
+/// ```
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// An instruction set of a register machine, implemented by its instruction type.
+///
+/// [`Display`] is used to print instructions in traces and disassemblies, e.g. `adv 3`.
+pub trait InstructionSet: Sized + Display {
+    /// Names of the registers, used by traces. The machine has one register per name.
+    const REGISTERS: &'static [&'static str];
+
+    /// Number of words an instruction takes up in bytecode.
+    const WIDTH: usize = 1;
+
+    /// Decodes an instruction from exactly [`Self::WIDTH`] words of bytecode.
+    /// Only needed for bytecode programs, see [`decode`].
+    #[allow(unused_variables)]
+    fn decode(words: &[i64]) -> Result<Self, String> {
+        Err("instruction set does not support bytecode".into())
+    }
+
+    /// Executes the instruction, returning what the machine should do next.
+    fn execute(&self, registers: &mut [i64]) -> Result<Effect, String>;
+}
+
+/// What a [`Machine`] does after executing an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Continue with the next instruction.
+    Next,
+    /// Continue at an absolute instruction address.
+    Jump(usize),
+    /// Continue at an address relative to the current instruction.
+    Offset(isize),
+    /// Emit a value and continue with the next instruction.
+    Output(i64),
+    /// Stop the machine.
+    Halt,
+}
+
+/// Why [`Machine::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The program halted, either explicitly or by moving the instruction pointer out of the program.
+    Halted,
+    /// The instruction pointer reached a breakpoint. The instruction at the breakpoint was not executed yet.
+    Breakpoint(usize),
+    /// The step limit of [`Machine::run_limited`] was reached.
+    StepLimit,
+}
+
+/// Decodes a bytecode program into instructions of [`InstructionSet::WIDTH`] words each.
+pub fn decode<I: InstructionSet>(words: &[i64]) -> Result<Vec<I>, VmError> {
+    if !words.len().is_multiple_of(I::WIDTH) {
+        return Err(VmError::Truncated {
+            address: words.len() / I::WIDTH,
+        });
+    }
+
+    words
+        .chunks(I::WIDTH)
+        .enumerate()
+        .map(|(address, words)| {
+            I::decode(words).map_err(|message| VmError::Decode { address, message })
+        })
+        .collect()
+}
+
+/// Prints a program with one numbered instruction per line.
+pub fn disassemble<I: InstructionSet>(program: &[I]) -> String {
+    let width = program.len().saturating_sub(1).to_string().len();
+
+    program
+        .iter()
+        .enumerate()
+        .map(|(address, instruction)| format!("{address:>width$}: {instruction}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Searches register values that are consumed one digit of `base` at a time, from the most significant digit.
+///
+/// Starting from 0, candidates are extended by one digit per round and kept if `accepts(value, round)` holds,
+/// for `rounds` rounds. This fits programs which output one value per digit of their input register, when the
+/// wanted output is known: in round `k`, a value is accepted if it outputs the last `k` values.
+///
+/// Returns all accepted values of the last round in ascending order.
+pub fn search_digits(
+    base: u64,
+    rounds: usize,
+    mut accepts: impl FnMut(u64, usize) -> bool,
+) -> Vec<u64> {
+    let mut candidates = vec![0];
+
+    for round in 1..=rounds {
+        candidates = candidates
+            .iter()
+            .flat_map(|candidate| (0..base).map(move |digit| candidate * base + digit))
+            .filter(|&value| accepts(value, round))
+            .collect();
+    }
+
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// State of a machine before executing an instruction, recorded by [`Machine::with_trace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Number of instructions executed before.
+    pub step: usize,
+    pub address: usize,
+    /// The instruction, as printed by its [`Display`] implementation.
+    pub instruction: String,
+    pub registers: Vec<i64>,
+}
+
+impl TraceEntry {
+    /// Formats the entry with register names, e.g. `3 @ 1: bxl 1 | a=729 b=0 c=0`.
+    pub fn to_string_with_names(&self, names: &[&str]) -> String {
+        let registers = names
+            .iter()
+            .zip(&self.registers)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "{} @ {}: {} | {registers}",
+            self.step, self.address, self.instruction
+        )
+    }
+}
+
+/// Runs a program of an [`InstructionSet`].
+#[derive(Debug, Clone)]
+pub struct Machine<I> {
+    program: Vec<I>,
+    registers: Vec<i64>,
+    ip: usize,
+    steps: usize,
+    halted: bool,
+    output: Vec<i64>,
+    breakpoints: HashSet<usize>,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl<I: InstructionSet> Machine<I> {
+    /// Creates a machine. Registers missing from `registers` start at 0.
+    pub fn new(program: Vec<I>, registers: &[i64]) -> Self {
+        let mut machine = Machine {
+            program,
+            registers: vec![],
+            ip: 0,
+            steps: 0,
+            halted: false,
+            output: vec![],
+            breakpoints: HashSet::new(),
+            trace: None,
+        };
+        machine.reset(registers);
+        machine
+    }
+
+    /// Creates a machine from a bytecode program, see [`decode`].
+    pub fn from_words(words: &[i64], registers: &[i64]) -> Result<Self, VmError> {
+        Ok(Machine::new(decode(words)?, registers))
+    }
+
+    /// Records the state before every executed instruction, see [`Machine::trace`].
+    #[must_use]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Restarts the program with new register values, keeping breakpoints and clearing output and trace.
+    pub fn reset(&mut self, registers: &[i64]) {
+        self.registers = vec![0; I::REGISTERS.len()];
+        for (register, &value) in self.registers.iter_mut().zip(registers) {
+            *register = value;
+        }
+        self.ip = 0;
+        self.steps = 0;
+        self.halted = false;
+        self.output.clear();
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn registers(&self) -> &[i64] {
+        &self.registers
+    }
+
+    /// Returns the value of the register named `name`.
+    ///
+    /// # Panics
+    /// Panics if the instruction set has no such register.
+    pub fn register(&self, name: &str) -> i64 {
+        let index = I::REGISTERS
+            .iter()
+            .position(|&register| register == name)
+            .unwrap_or_else(|| panic!("no register named \"{name}\""));
+        self.registers[index]
+    }
+
+    pub fn registers_mut(&mut self) -> &mut [i64] {
+        &mut self.registers
+    }
+
+    /// Address of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Number of executed instructions.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Values emitted by [`Effect::Output`] so far.
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Recorded states, empty unless the machine was created [`Machine::with_trace`].
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Pauses [`Machine::run`] before the instruction at `address` is executed.
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    /// Executes a single instruction. Returns `Some(Status::Halted)` if the machine halted before or during the step.
+    pub fn step(&mut self) -> Result<Option<Status>, VmError> {
+        if self.halted {
+            return Ok(Some(Status::Halted));
+        }
+
+        let Some(instruction) = self.program.get(self.ip) else {
+            self.halted = true;
+            return Ok(Some(Status::Halted));
+        };
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                step: self.steps,
+                address: self.ip,
+                instruction: instruction.to_string(),
+                registers: self.registers.clone(),
+            });
+        }
+
+        let effect = instruction
+            .execute(&mut self.registers)
+            .map_err(|message| VmError::Execute {
+                address: self.ip,
+                message,
+            })?;
+        self.steps += 1;
+
+        match effect {
+            Effect::Next => self.ip += 1,
+            Effect::Jump(address) => self.ip = address,
+            Effect::Offset(offset) => match self.ip.checked_add_signed(offset) {
+                Some(address) => self.ip = address,
+                None => self.halted = true,
+            },
+            Effect::Output(value) => {
+                self.output.push(value);
+                self.ip += 1;
+            }
+            Effect::Halt => self.halted = true,
+        }
+
+        if self.ip >= self.program.len() {
+            self.halted = true;
+        }
+
+        Ok(self.halted.then_some(Status::Halted))
+    }
+
+    /// Runs until the program halts or reaches a breakpoint. A breakpoint at the current address is
+    /// stepped over, so calling `run` again resumes the program.
+    pub fn run(&mut self) -> Result<Status, VmError> {
+        self.run_limited(usize::MAX)
+    }
+
+    /// Like [`Machine::run`], but returns [`Status::StepLimit`] after executing `max_steps` instructions.
+    pub fn run_limited(&mut self, max_steps: usize) -> Result<Status, VmError> {
+        for i in 0..max_steps {
+            if i > 0 && self.breakpoints.contains(&self.ip) && !self.halted {
+                return Ok(Status::Breakpoint(self.ip));
+            }
+
+            if let Some(status) = self.step()? {
+                return Ok(status);
+            }
+        }
+
+        Ok(Status::StepLimit)
+    }
+
+    /// Prints the program like [`disassemble`], marking the next instruction with `>` and breakpoints with `*`.
+    pub fn disassemble(&self) -> String {
+        disassemble(&self.program)
+            .lines()
+            .enumerate()
+            .map(|(address, line)| {
+                let ip = if address == self.ip { '>' } else { ' ' };
+                let breakpoint = if self.breakpoints.contains(&address) {
+                    '*'
+                } else {
+                    ' '
+                };
+                format!("{ip}{breakpoint} {line}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// An error which can be returned when decoding or running a program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    /// The bytecode ends in the middle of the instruction at `address`.
+    Truncated { address: usize },
+    /// The instruction at `address` could not be decoded.
+    Decode { address: usize, message: String },
+    /// The instruction at `address` failed to execute.
+    Execute { address: usize, message: String },
+}
+
+impl Error for VmError {}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::Truncated { address } => {
+                write!(f, "bytecode ends within the instruction at {address}")
+            }
+            VmError::Decode { address, message } => {
+                write!(
+                    f,
+                    "failed to decode the instruction at {address}: {message}"
+                )
+            }
+            VmError::Execute { address, message } => {
+                write!(
+                    f,
+                    "failed to execute the instruction at {address}: {message}"
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, search_digits, Effect, InstructionSet, Machine, Status, VmError};
+    use std::fmt::{self, Display};
+
+    /// The 3-bit computer of 2024 day 17.
+    #[derive(Debug, Clone, Copy)]
+    struct Chip {
+        opcode: i64,
+        operand: i64,
+    }
+
+    const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+    impl InstructionSet for Chip {
+        const REGISTERS: &'static [&'static str] = &["a", "b", "c"];
+        const WIDTH: usize = 2;
+
+        fn decode(words: &[i64]) -> Result<Self, String> {
+            match words {
+                &[opcode @ 0..=7, operand @ 0..=7] => Ok(Chip { opcode, operand }),
+                _ => Err(format!("invalid words {words:?}")),
+            }
+        }
+
+        fn execute(&self, r: &mut [i64]) -> Result<Effect, String> {
+            let combo = match self.operand {
+                0..=3 => self.operand,
+                4..=6 => r[self.operand as usize - 4],
+                _ => return Err("invalid combo operand 7".into()),
+            };
+
+            match self.opcode {
+                0 => r[0] >>= combo,
+                1 => r[1] ^= self.operand,
+                2 => r[1] = combo % 8,
+                3 if r[0] != 0 => return Ok(Effect::Jump(self.operand as usize / 2)),
+                3 => {}
+                4 => r[1] ^= r[2],
+                5 => return Ok(Effect::Output(combo % 8)),
+                6 => r[1] = r[0] >> combo,
+                _ => r[2] = r[0] >> combo,
+            }
+
+            Ok(Effect::Next)
+        }
+    }
+
+    impl Display for Chip {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} {}", MNEMONICS[self.opcode as usize], self.operand)
+        }
+    }
+
+    #[test]
+    fn runs_programs() {
+        let mut machine = Machine::<Chip>::from_words(&[0, 1, 5, 4, 3, 0], &[729]).unwrap();
+
+        assert_eq!(machine.run(), Ok(Status::Halted));
+        assert_eq!(machine.output(), &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(machine.register("a"), 0);
+        assert!(machine.is_halted());
+        assert_eq!(machine.step(), Ok(Some(Status::Halted)));
+    }
+
+    #[test]
+    fn stops_at_breakpoints_and_limits() {
+        let mut machine = Machine::<Chip>::from_words(&[0, 1, 5, 4, 3, 0], &[729]).unwrap();
+        machine.add_breakpoint(1);
+
+        assert_eq!(machine.run(), Ok(Status::Breakpoint(1)));
+        assert_eq!(machine.output(), &[]);
+        assert_eq!(machine.run(), Ok(Status::Breakpoint(1)));
+        assert_eq!(machine.output(), &[4]);
+
+        machine.remove_breakpoint(1);
+        assert_eq!(machine.run_limited(3), Ok(Status::StepLimit));
+        assert_eq!(machine.output(), &[4, 6]);
+        assert_eq!(machine.steps(), 7);
+
+        machine.reset(&[729]);
+        assert_eq!((machine.ip(), machine.steps()), (0, 0));
+        assert_eq!(machine.output(), &[]);
+    }
+
+    #[test]
+    fn traces_execution() {
+        let mut machine = Machine::<Chip>::from_words(&[0, 1, 5, 4, 3, 0], &[4])
+            .unwrap()
+            .with_trace();
+        machine.run().unwrap();
+
+        let trace: Vec<_> = machine
+            .trace()
+            .iter()
+            .map(|entry| entry.to_string_with_names(Chip::REGISTERS))
+            .collect();
+
+        assert_eq!(
+            trace,
+            vec![
+                "0 @ 0: adv 1 | a=4 b=0 c=0",
+                "1 @ 1: out 4 | a=2 b=0 c=0",
+                "2 @ 2: jnz 0 | a=2 b=0 c=0",
+                "3 @ 0: adv 1 | a=2 b=0 c=0",
+                "4 @ 1: out 4 | a=1 b=0 c=0",
+                "5 @ 2: jnz 0 | a=1 b=0 c=0",
+                "6 @ 0: adv 1 | a=1 b=0 c=0",
+                "7 @ 1: out 4 | a=0 b=0 c=0",
+                "8 @ 2: jnz 0 | a=0 b=0 c=0",
+            ]
+        );
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        let mut machine = Machine::<Chip>::from_words(&[0, 1, 5, 4, 3, 0], &[]).unwrap();
+        machine.add_breakpoint(2);
+
+        assert_eq!(
+            machine.disassemble(),
+            ">  0: adv 1\n   1: out 4\n * 2: jnz 0"
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            decode::<Chip>(&[0, 1, 5]).unwrap_err(),
+            VmError::Truncated { address: 1 }
+        );
+        assert_eq!(
+            decode::<Chip>(&[0, 1, 8, 0]).unwrap_err(),
+            VmError::Decode {
+                address: 1,
+                message: "invalid words [8, 0]".into()
+            }
+        );
+
+        let mut machine = Machine::<Chip>::from_words(&[1, 2, 5, 7], &[]).unwrap();
+        assert_eq!(
+            machine.run().unwrap_err().to_string(),
+            "failed to execute the instruction at 1: invalid combo operand 7"
+        );
+    }
+
+    #[test]
+    fn searches_digits_backwards() {
+        let program = [0, 3, 5, 4, 3, 0];
+        let mut machine = Machine::<Chip>::from_words(&program, &[]).unwrap();
+
+        let found = search_digits(8, program.len(), |a, round| {
+            machine.reset(&[a as i64]);
+            machine.run().unwrap();
+            machine.output() == &program[program.len() - round..]
+        });
+
+        assert_eq!(found.first(), Some(&117_440));
+    }
+}